@@ -1,8 +1,10 @@
 use std::pin::Pin;
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
 
 use bytes::Bytes;
 use chrono::{DateTime, NaiveDateTime, Utc};
-use futures::TryStreamExt;
+use futures::{stream, TryStreamExt};
 use libsql_replication::frame::Frame;
 use libsql_replication::replicator::{map_frame_err, Error, ReplicatorClient};
 use libsql_replication::rpc::replication::replication_log_client::ReplicationLogClient;
@@ -13,7 +15,7 @@ use libsql_replication::rpc::replication::{
 use tokio_stream::{Stream, StreamExt};
 use tonic::metadata::{AsciiMetadataValue, BinaryMetadataValue};
 use tonic::transport::Channel;
-use tonic::Request;
+use tonic::{Code, Request};
 
 use crate::connection::config::DatabaseConfig;
 use crate::metrics::{
@@ -23,13 +25,102 @@ use crate::namespace::meta_store::MetaStoreHandle;
 use crate::namespace::NamespaceName;
 use crate::replication::FrameNo;
 
+/// Controls how the replication client recovers from a dropped `Channel` or a failed RPC:
+/// exponential backoff between attempts, capped at `max_delay`. Unlike the scheduler's
+/// `RetryPolicy`, there is no limit on the number of attempts — giving up on a replication
+/// stream means the replica goes stale forever, so it keeps trying to reach the primary until
+/// it comes back rather than surfacing a permanent error to the caller.
+#[derive(Debug, Clone)]
+pub struct ReconnectPolicy {
+    pub base_delay: Duration,
+    pub max_delay: Duration,
+}
+
+impl Default for ReconnectPolicy {
+    fn default() -> Self {
+        Self {
+            base_delay: Duration::from_millis(100),
+            max_delay: Duration::from_secs(10),
+        }
+    }
+}
+
+impl ReconnectPolicy {
+    /// Backoff delay for the given (1-indexed) attempt, computed as
+    /// `min(base_delay * 2^attempt, max_delay)`.
+    fn backoff(&self, attempt: u32) -> Duration {
+        let factor = 1u32.checked_shl(attempt).unwrap_or(u32::MAX);
+        self.base_delay.saturating_mul(factor).min(self.max_delay)
+    }
+}
+
+/// Whether a failed RPC is worth retrying (transport hiccup, primary restarting, ...), as
+/// opposed to a permanent error the caller needs to know about (e.g. the namespace was
+/// deleted).
+fn is_transient(status: &tonic::Status) -> bool {
+    matches!(
+        status.code(),
+        Code::Unavailable
+            | Code::Cancelled
+            | Code::DeadlineExceeded
+            | Code::Aborted
+            | Code::Unknown
+            | Code::ResourceExhausted
+    )
+}
+
+fn build_request<T>(namespace: &NamespaceName, session_token: &Mutex<Option<Bytes>>, msg: T) -> Request<T> {
+    let mut req = Request::new(msg);
+    req.metadata_mut().insert_bin(
+        NAMESPACE_METADATA_KEY,
+        BinaryMetadataValue::from_bytes(namespace.as_slice()),
+    );
+
+    if let Some(token) = session_token.lock().unwrap().clone() {
+        // SAFETY: we always check the session token
+        req.metadata_mut().insert(SESSION_TOKEN_KEY, unsafe {
+            AsciiMetadataValue::from_shared_unchecked(token)
+        });
+    }
+
+    req
+}
+
+/// Records the replication-latency metrics for a single frame, exactly as before: this just
+/// factors the logic out so it can be shared between the initial connection and every
+/// reconnect.
+fn record_replication_latency(f: &libsql_replication::rpc::replication::Frame) {
+    match f.timestamp {
+        Some(ts_millis) => {
+            if let Some(ts_millis) = NaiveDateTime::from_timestamp_millis(ts_millis) {
+                let commited_at = DateTime::<Utc>::from_naive_utc_and_offset(ts_millis, Utc);
+                let lat = Utc::now() - commited_at;
+                match lat.to_std() {
+                    Ok(lat) => {
+                        // we can record negative values if the clocks are out-of-sync. There is not
+                        // point in recording those values.
+                        REPLICATION_LATENCY.record(lat);
+                    }
+                    Err(_) => {
+                        REPLICATION_LATENCY_OUT_OF_SYNC.increment(1);
+                    }
+                }
+            }
+        }
+        None => REPLICATION_LATENCY_CACHE_MISS.increment(1),
+    }
+}
+
 pub struct Client {
     client: ReplicationLogClient<Channel>,
     namespace: NamespaceName,
-    session_token: Option<Bytes>,
+    session_token: Arc<Mutex<Option<Bytes>>>,
     meta_store_handle: MetaStoreHandle,
-    // the primary current replication index, as reported by the last handshake
-    pub primary_replication_index: Option<FrameNo>,
+    // the primary current replication index, as reported by the last handshake. Shared behind
+    // an `Arc` so the reconnect loop driving a live frame stream (which outlives the `&mut
+    // self` call that created it) can refresh it too.
+    primary_replication_index: Arc<Mutex<Option<FrameNo>>>,
+    reconnect_policy: ReconnectPolicy,
 }
 
 impl Client {
@@ -41,31 +132,208 @@ impl Client {
         Ok(Self {
             namespace,
             client,
-            session_token: None,
+            session_token: Default::default(),
             meta_store_handle,
-            primary_replication_index: None,
+            primary_replication_index: Default::default(),
+            reconnect_policy: ReconnectPolicy::default(),
         })
     }
 
+    /// Overrides the default backoff policy used when reconnecting to the primary.
+    pub fn with_reconnect_policy(mut self, policy: ReconnectPolicy) -> Self {
+        self.reconnect_policy = policy;
+        self
+    }
+
+    pub fn primary_replication_index(&self) -> Option<FrameNo> {
+        *self.primary_replication_index.lock().unwrap()
+    }
+
     fn make_request<T>(&self, msg: T) -> Request<T> {
-        let mut req = Request::new(msg);
-        req.metadata_mut().insert_bin(
-            NAMESPACE_METADATA_KEY,
-            BinaryMetadataValue::from_bytes(self.namespace.as_slice()),
-        );
-
-        if let Some(token) = self.session_token.clone() {
-            // SAFETY: we always check the session token
-            req.metadata_mut().insert(SESSION_TOKEN_KEY, unsafe {
-                AsciiMetadataValue::from_shared_unchecked(token)
-            });
+        build_request(&self.namespace, &self.session_token, msg)
+    }
+
+    pub(crate) fn reset_token(&mut self) {
+        self.session_token.lock().unwrap().take();
+    }
+
+    /// Performs the `hello` RPC and applies its response (session token, replication index,
+    /// config) to the given shared state, retrying with backoff on transient failures and
+    /// forcing a clean re-handshake if the primary rejects our session token.
+    async fn do_handshake(
+        client: &mut ReplicationLogClient<Channel>,
+        namespace: &NamespaceName,
+        session_token: &Arc<Mutex<Option<Bytes>>>,
+        primary_replication_index: &Arc<Mutex<Option<FrameNo>>>,
+        meta_store_handle: &MetaStoreHandle,
+        reconnect_policy: &ReconnectPolicy,
+    ) -> Result<HelloResponse, Error> {
+        let mut attempt = 0;
+        loop {
+            let req = build_request(namespace, session_token, HelloRequest::new());
+            match client.hello(req).await {
+                Ok(resp) => {
+                    let hello = resp.into_inner();
+                    verify_session_token(&hello.session_token).map_err(Error::Client)?;
+                    *primary_replication_index.lock().unwrap() = hello.current_replication_index;
+                    session_token
+                        .lock()
+                        .unwrap()
+                        .replace(hello.session_token.clone());
+
+                    if let Some(config) = &hello.config {
+                        meta_store_handle
+                            .store(DatabaseConfig::from(config))
+                            .await
+                            .map_err(|e| Error::Internal(e.into()))?;
+
+                        tracing::debug!("replica config has been updated");
+                    } else {
+                        tracing::debug!("no config passed in handshake");
+                    }
+
+                    return Ok(hello);
+                }
+                Err(status) if status.code() == Code::Unauthenticated => {
+                    // the primary no longer recognizes our session: drop it so the next
+                    // attempt performs a clean handshake.
+                    session_token.lock().unwrap().take();
+                    attempt += 1;
+                    let delay = reconnect_policy.backoff(attempt);
+                    tracing::warn!(attempt, ?delay, "session rejected by primary, re-handshaking");
+                    tokio::time::sleep(delay).await;
+                }
+                Err(status) if is_transient(&status) => {
+                    attempt += 1;
+                    let delay = reconnect_policy.backoff(attempt);
+                    tracing::warn!(error = %status, attempt, ?delay, "handshake failed, retrying");
+                    tokio::time::sleep(delay).await;
+                }
+                Err(status) => return Err(status.into()),
+            }
         }
+    }
+}
 
-        req
+/// State threaded through the `log_entries` reconnect loop driving [`Client::next_frames`]'s
+/// stream. Everything here is either cheap to clone (the RPC client, a couple of handles) or
+/// shared with the owning [`Client`] through an `Arc`, since the stream outlives the `&mut
+/// self` call that created it.
+struct FrameStreamState {
+    client: ReplicationLogClient<Channel>,
+    namespace: NamespaceName,
+    session_token: Arc<Mutex<Option<Bytes>>>,
+    primary_replication_index: Arc<Mutex<Option<FrameNo>>>,
+    meta_store_handle: MetaStoreHandle,
+    reconnect_policy: ReconnectPolicy,
+    /// Offset of the next frame we expect from the primary. Advances with every frame
+    /// delivered to the caller; a reconnect resumes `log_entries` from here via the existing
+    /// `LogOffset { next_offset }` mechanism, so no already-applied frame is replayed.
+    next_offset: FrameNo,
+    /// The currently open frame stream, or `None` while we're (re)connecting.
+    inner: Option<Pin<Box<dyn Stream<Item = Result<Frame, Error>> + Send>>>,
+    attempt: u32,
+}
+
+/// Outcome of a single (re)connect attempt: either we're connected, the failure is worth
+/// retrying with backoff, or it's permanent and the caller needs to know about it instead of
+/// being retried forever.
+enum ReconnectOutcome {
+    Connected,
+    Retry(Error),
+    Permanent(Error),
+}
+
+/// (Re)establishes `state.inner` by re-running the handshake and opening a fresh
+/// `log_entries` stream at `state.next_offset`.
+async fn open_log_entries(state: &mut FrameStreamState) -> ReconnectOutcome {
+    // `do_handshake` already retries every transient failure (and re-handshakes on a rejected
+    // session token) internally, forever; it only returns an `Err` for a non-transient status,
+    // an invalid session token, or a failure to store the received config, and every one of
+    // those is treated as permanent here.
+    if let Err(e) = Client::do_handshake(
+        &mut state.client,
+        &state.namespace,
+        &state.session_token,
+        &state.primary_replication_index,
+        &state.meta_store_handle,
+        &state.reconnect_policy,
+    )
+    .await
+    {
+        return ReconnectOutcome::Permanent(e);
     }
 
-    pub(crate) fn reset_token(&mut self) {
-        self.session_token = None;
+    let req = build_request(
+        &state.namespace,
+        &state.session_token,
+        LogOffset {
+            next_offset: state.next_offset,
+        },
+    );
+    match state.client.log_entries(req).await {
+        Ok(resp) => {
+            let stream = resp
+                .into_inner()
+                .inspect_ok(record_replication_latency)
+                .map(map_frame_err);
+            state.inner = Some(Box::pin(stream));
+            ReconnectOutcome::Connected
+        }
+        Err(status) if is_transient(&status) => ReconnectOutcome::Retry(status.into()),
+        Err(status) => ReconnectOutcome::Permanent(status.into()),
+    }
+}
+
+/// Drives one item out of the reconnecting `log_entries` stream: pulls from `state.inner`,
+/// transparently reconnecting (re-handshake + resume from `state.next_offset`) with backoff
+/// whenever the stream isn't open yet or an item comes back as an error, whatever its kind. A
+/// permanent failure while reconnecting (deleted namespace, permission denied, ...) is surfaced
+/// to the caller instead of being retried forever. The stream only ever ends when the primary closes it cleanly.
+async fn drive_frame_stream(
+    mut state: FrameStreamState,
+) -> Option<(Result<Frame, Error>, FrameStreamState)> {
+    loop {
+        if state.inner.is_none() {
+            match open_log_entries(&mut state).await {
+                ReconnectOutcome::Connected => state.attempt = 0,
+                ReconnectOutcome::Retry(e) => {
+                    state.attempt += 1;
+                    let delay = state.reconnect_policy.backoff(state.attempt);
+                    tracing::warn!(
+                        error = %e,
+                        attempt = state.attempt,
+                        ?delay,
+                        "failed to (re)connect to primary, retrying"
+                    );
+                    tokio::time::sleep(delay).await;
+                    continue;
+                }
+                ReconnectOutcome::Permanent(e) => {
+                    tracing::error!(error = %e, "permanent error reconnecting to primary, giving up");
+                    return Some((Err(e), state));
+                }
+            }
+        }
+
+        match state.inner.as_mut().expect("just (re)connected above").next().await {
+            Some(Ok(frame)) => {
+                state.next_offset += 1;
+                return Some((Ok(frame), state));
+            }
+            Some(Err(e)) => {
+                tracing::warn!(
+                    error = %e,
+                    next_offset = state.next_offset,
+                    "replication stream interrupted, reconnecting"
+                );
+                state.inner = None;
+                state.attempt += 1;
+                let delay = state.reconnect_policy.backoff(state.attempt);
+                tokio::time::sleep(delay).await;
+            }
+            None => return None,
+        }
     }
 }
 
@@ -76,74 +344,61 @@ impl ReplicatorClient for Client {
     #[tracing::instrument(skip(self))]
     async fn handshake(&mut self) -> Result<Option<HelloResponse>, Error> {
         tracing::info!("Attempting to perform handshake with primary.");
-        let req = self.make_request(HelloRequest::new());
-        let resp = self.client.hello(req).await?;
-        let hello = resp.into_inner();
-        verify_session_token(&hello.session_token).map_err(Error::Client)?;
-        self.primary_replication_index = hello.current_replication_index;
-        self.session_token.replace(hello.session_token.clone());
-
-        if let Some(config) = &hello.config {
-            self.meta_store_handle
-                .store(DatabaseConfig::from(config))
-                .await
-                .map_err(|e| Error::Internal(e.into()))?;
-
-            tracing::debug!("replica config has been updated");
-        } else {
-            tracing::debug!("no config passed in handshake");
-        }
-
+        let hello = Self::do_handshake(
+            &mut self.client,
+            &self.namespace,
+            &self.session_token,
+            &self.primary_replication_index,
+            &self.meta_store_handle,
+            &self.reconnect_policy,
+        )
+        .await?;
         tracing::trace!("handshake completed");
 
         Ok(Some(hello))
     }
 
     async fn next_frames(&mut self, next_offset: FrameNo) -> Result<Self::FrameStream, Error> {
-        dbg!(next_offset);
-        let offset = LogOffset { next_offset };
-        let req = self.make_request(offset);
-        let stream = self
-            .client
-            .log_entries(req)
-            .await?
-            .into_inner()
-            .inspect_ok(|f| {
-                match f.timestamp {
-                    Some(ts_millis) => {
-                        if let Some(ts_millis) = NaiveDateTime::from_timestamp_millis(ts_millis) {
-                            let commited_at =
-                                DateTime::<Utc>::from_naive_utc_and_offset(ts_millis, Utc);
-                            let lat = Utc::now() - commited_at;
-                            match lat.to_std() {
-                                Ok(lat) => {
-                                    // we can record negative values if the clocks are out-of-sync. There is not
-                                    // point in recording those values.
-                                    REPLICATION_LATENCY.record(lat);
-                                }
-                                Err(_) => {
-                                    REPLICATION_LATENCY_OUT_OF_SYNC.increment(1);
-                                }
-                            }
-                        }
-                    }
-                    None => REPLICATION_LATENCY_CACHE_MISS.increment(1),
-                }
-            })
-            .map(map_frame_err);
+        tracing::trace!(next_offset, "requesting next frames");
+        let state = FrameStreamState {
+            client: self.client.clone(),
+            namespace: self.namespace.clone(),
+            session_token: self.session_token.clone(),
+            primary_replication_index: self.primary_replication_index.clone(),
+            meta_store_handle: self.meta_store_handle.clone(),
+            reconnect_policy: self.reconnect_policy.clone(),
+            next_offset,
+            inner: None,
+            attempt: 0,
+        };
 
-        Ok(Box::pin(stream))
+        Ok(Box::pin(stream::unfold(state, drive_frame_stream)))
     }
 
     async fn snapshot(&mut self, next_offset: FrameNo) -> Result<Self::FrameStream, Error> {
-        let offset = LogOffset { next_offset };
-        let req = self.make_request(offset);
-        let stream = self
-            .client
-            .snapshot(req)
-            .await?
-            .into_inner()
-            .map(map_frame_err);
-        Ok(Box::pin(stream))
+        let mut attempt = 0;
+        loop {
+            let req = self.make_request(LogOffset { next_offset });
+            match self.client.snapshot(req).await {
+                Ok(resp) => {
+                    let stream = resp.into_inner().map(map_frame_err);
+                    return Ok(Box::pin(stream));
+                }
+                Err(status) if status.code() == Code::Unauthenticated => {
+                    self.reset_token();
+                    attempt += 1;
+                    let delay = self.reconnect_policy.backoff(attempt);
+                    tracing::warn!(attempt, ?delay, "session rejected by primary, retrying without session token");
+                    tokio::time::sleep(delay).await;
+                }
+                Err(status) if is_transient(&status) => {
+                    attempt += 1;
+                    let delay = self.reconnect_policy.backoff(attempt);
+                    tracing::warn!(error = %status, attempt, ?delay, "snapshot request failed, retrying");
+                    tokio::time::sleep(delay).await;
+                }
+                Err(status) => return Err(status.into()),
+            }
+        }
     }
 }
@@ -1,25 +1,164 @@
 use std::collections::{HashMap, HashSet, VecDeque};
 use std::fmt;
+use std::time::{Duration, Instant};
 
 use anyhow::Result;
 use crossbeam::channel::{Sender, TrySendError};
 use smallvec::SmallVec;
 use tokio::sync::mpsc::{UnboundedReceiver as TokioReceiver, UnboundedSender as TokioSender};
+use tokio::sync::{oneshot, watch};
 
 use crate::job::Job;
-use crate::messages::Responder;
+use crate::messages::{Message, Responder};
 use crate::statements::Statements;
 
 pub type ClientId = usize;
 
-#[derive(Default)]
+/// Amount of deficit granted to a client, scaled by its weight, every time it is visited by
+/// the deficit round-robin scheduler. A job costs `1` unit of deficit to dispatch.
+const DRR_QUANTUM: usize = 1;
+
+/// Controls how many times, and how long, the scheduler waits before retrying a job that
+/// failed with a transient error.
+#[derive(Debug, Clone)]
+pub struct RetryPolicy {
+    pub max_attempts: u32,
+    pub base_delay: Duration,
+    pub max_delay: Duration,
+}
+
+impl Default for RetryPolicy {
+    fn default() -> Self {
+        Self {
+            max_attempts: 3,
+            base_delay: Duration::from_millis(50),
+            max_delay: Duration::from_secs(5),
+        }
+    }
+}
+
+impl RetryPolicy {
+    /// Backoff delay for the given (1-indexed) attempt, computed as
+    /// `min(base_delay * 2^attempt, max_delay)`.
+    fn backoff(&self, attempt: u32) -> Duration {
+        let factor = 1u32.checked_shl(attempt).unwrap_or(u32::MAX);
+        self.base_delay.saturating_mul(factor).min(self.max_delay)
+    }
+}
+
+/// Why a dispatched job failed to run to completion, as reported back by the worker pool via
+/// [`UpdateStateMessage::Failed`].
+#[derive(Debug, Clone)]
+pub enum FailureKind {
+    /// The active transaction's channel was closed before the job could be sent to it.
+    TxnClosed,
+    /// The database reported that it was busy/locked.
+    Busy,
+    /// Any other transient failure, carrying a human-readable reason.
+    Other(String),
+}
+
+impl fmt::Display for FailureKind {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            FailureKind::TxnClosed => write!(f, "transaction channel closed"),
+            FailureKind::Busy => write!(f, "database busy"),
+            FailureKind::Other(reason) => write!(f, "{reason}"),
+        }
+    }
+}
+
+/// How the scheduler should treat jobs that are still queued (not yet dispatched) when a
+/// shutdown is requested via [`Scheduler::shutdown_handle`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum ShutdownMode {
+    /// Keep dispatching queued jobs until every queue is empty, just stop accepting new ones.
+    #[default]
+    Drain,
+    /// Immediately cancel every queued (not yet dispatched) job, answering its `Responder`
+    /// with a cancellation error. Jobs already dispatched to the worker pool still run to
+    /// completion.
+    Abort,
+}
+
+/// A [`Job`] paired with its retry bookkeeping.
+#[derive(Debug)]
+pub struct QueuedJob {
+    job: Job,
+    retry: RetryPolicy,
+    attempt: u32,
+}
+
 struct ClientQueue {
-    queue: VecDeque<Job>,
+    queue: VecDeque<QueuedJob>,
     /// Sender to the active transaction for this client.
     /// On ready state, jobs for this client should be sent to this channel instead of the global queue.
     active_txn: Option<Sender<Job>>,
     /// The client for this queue has disconnected
     should_close: bool,
+    /// Relative scheduling weight for deficit round-robin; higher means a bigger share of the
+    /// dispatch order relative to other clients. Defaults to `1`.
+    weight: usize,
+    /// Deficit round-robin counter: grows by `DRR_QUANTUM * weight` every time this client is
+    /// visited with queued work (ready or not), and is spent (by `1`) every time a job is dispatched. Reset to `0`
+    /// whenever the client's queue goes empty, so idle clients don't bank deficit.
+    deficit: usize,
+    /// Last time this queue's state changed (job enqueued, dispatched, or became ready).
+    last_activity: Instant,
+    /// Retry policy and attempt count of the job currently in flight for this client, if any.
+    /// Consulted when a matching `UpdateStateMessage::Failed` comes back.
+    in_flight_retry: Option<(RetryPolicy, u32)>,
+}
+
+impl Default for ClientQueue {
+    fn default() -> Self {
+        Self {
+            queue: Default::default(),
+            active_txn: None,
+            should_close: false,
+            weight: 1,
+            deficit: 0,
+            last_activity: Instant::now(),
+            in_flight_retry: None,
+        }
+    }
+}
+
+/// Coarse-grained state of a client's queue, for introspection purposes.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum WorkerState {
+    /// The client has a job in flight and is waiting for it to complete.
+    Busy,
+    /// The client is ready to receive work but its queue is empty.
+    Idle,
+    /// The client has queued work waiting for its turn in the scheduling order.
+    Throttled,
+    /// The client has disconnected and its queue has drained.
+    Done,
+}
+
+impl fmt::Display for WorkerState {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let s = match self {
+            WorkerState::Busy => "busy",
+            WorkerState::Idle => "idle",
+            WorkerState::Throttled => "throttled",
+            WorkerState::Done => "done",
+        };
+        f.write_str(s)
+    }
+}
+
+/// A point-in-time snapshot of a single client queue, returned by
+/// [`UpdateStateMessage::Report`].
+#[derive(Debug, Clone)]
+pub struct QueueStatus {
+    pub client_id: ClientId,
+    pub state: WorkerState,
+    pub queued_len: usize,
+    pub in_flight: bool,
+    pub has_active_txn: bool,
+    pub last_activity: Instant,
 }
 
 #[derive(Debug)]
@@ -27,12 +166,24 @@ pub enum UpdateStateMessage {
     Ready(ClientId),
     TxnBegin(ClientId, Sender<Job>),
     TxnEnded(ClientId),
+    /// Ask the scheduler to snapshot the status of every client queue it knows about. Used by
+    /// admin/introspection endpoints instead of intrusive logging.
+    Report(oneshot::Sender<Vec<QueueStatus>>),
+    /// Reported by the worker pool when a dispatched job failed. The scheduler decides whether
+    /// to retry it with backoff or route it to the dead letter path, based on the retry
+    /// bookkeeping it kept for the client's in-flight job.
+    Failed(ClientId, Job, FailureKind),
+    /// Internal message sent by the backoff timer once a failed job is ready to be retried.
+    Requeue(ClientId, QueuedJob),
 }
 
 #[derive(Debug)]
 pub enum Action {
     Disconnect,
     Execute(Statements),
+    /// Set this client's deficit round-robin weight, controlling its relative share of the
+    /// dispatch order. Takes effect on the client's next scheduling pass.
+    SetWeight(usize),
 }
 
 pub struct ServerMessage {
@@ -64,6 +215,17 @@ pub struct Scheduler {
     ready_set: HashSet<ClientId>,
     /// Set of endpoints that have some work in their queue
     has_work_set: HashSet<ClientId>,
+    /// Clients currently known to have queued work, in deficit round-robin visiting order.
+    /// Kept in sync with `has_work_set`: clients are appended when they gain work and dropped
+    /// once their queue empties.
+    active_ring: VecDeque<ClientId>,
+    /// How queued-but-not-yet-dispatched jobs are treated once a shutdown is requested.
+    shutdown_mode: ShutdownMode,
+    /// Flipped to `true` by [`Scheduler::shutdown_handle`]'s sender to request a coordinated
+    /// shutdown of the scheduling loop.
+    must_exit: watch::Receiver<bool>,
+    /// Kept around so `shutdown_handle` can hand out more senders than the first caller.
+    must_exit_tx: watch::Sender<bool>,
 }
 
 impl Scheduler {
@@ -72,6 +234,7 @@ impl Scheduler {
         job_receiver: TokioReceiver<ServerMessage>,
     ) -> Result<Self> {
         let (update_state_sender, update_state_receiver) = tokio::sync::mpsc::unbounded_channel();
+        let (must_exit_tx, must_exit) = watch::channel(false);
         Ok(Self {
             worker_pool_sender,
             queues: Default::default(),
@@ -80,56 +243,123 @@ impl Scheduler {
             job_receiver,
             ready_set: Default::default(),
             has_work_set: Default::default(),
+            active_ring: Default::default(),
+            shutdown_mode: ShutdownMode::default(),
+            must_exit,
+            must_exit_tx,
         })
     }
 
-    /// push some work to the gobal queue
+    /// Returns a sender that can be used to push [`UpdateStateMessage`]s to this scheduler,
+    /// e.g. to request a [`QueueStatus`] report from outside the scheduling loop.
+    pub fn update_state_sender(&self) -> TokioSender<UpdateStateMessage> {
+        self.update_state_sender.clone()
+    }
+
+    /// Sets how queued-but-not-yet-dispatched jobs are treated once a shutdown is requested.
+    /// Defaults to [`ShutdownMode::Drain`].
+    pub fn with_shutdown_mode(mut self, mode: ShutdownMode) -> Self {
+        self.shutdown_mode = mode;
+        self
+    }
+
+    /// Returns a handle the caller can flip to `true` to request a coordinated shutdown:
+    /// the scheduler stops accepting new jobs, optionally cancels queued-but-not-dispatched
+    /// ones (see [`ShutdownMode`]), and `start`'s future resolves once every queue has
+    /// drained.
+    pub fn shutdown_handle(&self) -> watch::Sender<bool> {
+        self.must_exit_tx.clone()
+    }
+
+    /// Dispatch queued jobs in deficit round-robin order so a client with a flooded queue
+    /// can't starve the others: each queued client's deficit grows by `DRR_QUANTUM * weight`
+    /// every visit, and it's spent a job at a time (pop, dispatch, `deficit -= 1`) for as long
+    /// as deficit remains and its queue isn't empty, so a higher-weight client can burst through
+    /// more of its backlog per visit than a lower-weight one. Clients keep the existing "one
+    /// in-flight job" invariant: a client leaves `ready_set` the moment it dispatches to the
+    /// shared worker pool, ending its burst for this pass even with deficit still banked.
     fn schedule_work(&mut self) {
         let mut not_waiting = SmallVec::<[ClientId; 16]>::new();
         let mut not_ready = SmallVec::<[ClientId; 16]>::new();
 
-        for client_id in self.ready_set.intersection(&self.has_work_set).copied() {
+        // bring the ring up to date with clients that just gained work
+        for client_id in self.has_work_set.iter().copied() {
+            if !self.active_ring.contains(&client_id) {
+                self.active_ring.push_back(client_id);
+            }
+        }
+
+        // visit every client currently in the ring exactly once this pass
+        for _ in 0..self.active_ring.len() {
+            let Some(client_id) = self.active_ring.pop_front() else {
+                break;
+            };
+
             let Some(queue) = self.queues.get_mut(&client_id) else {
                 not_ready.push(client_id);
                 not_waiting.push(client_id);
-                continue
+                continue;
             };
 
-            let Some(mut job) = queue.queue.pop_front() else {
+            if queue.queue.is_empty() {
+                // idle queue: drop out of the ring and reset its deficit so it doesn't bank
+                // credit while there's nothing to spend it on.
+                queue.deficit = 0;
                 not_waiting.push(client_id);
-                continue
-            };
+                continue;
+            }
 
-            not_ready.push(client_id);
+            queue.deficit += DRR_QUANTUM * queue.weight;
 
-            // there is an active transaction, so we should send it there
-            if let Some(ref sender) = queue.active_txn {
-                job = match sender.try_send(job) {
-                    Ok(_) => {
-                        continue;
-                    }
-                    // the transaction channel was closed before we were notified, we'll send
-                    // to the main queue instead
-                    Err(TrySendError::Disconnected(job)) => {
-                        queue.active_txn.take();
-                        job
-                    }
-                    Err(TrySendError::Full(_)) => {
-                        unreachable!("txn channel should never be full")
-                    }
-                };
+            // burst-dispatch while ready, deficit remains, and there's still work queued. A job
+            // handed to an active transaction's own channel doesn't touch `ready_set` (each
+            // transaction statement gets its own turn there), but one sent to the shared worker
+            // pool does, which ends the burst early even if deficit is still banked.
+            let mut dispatched_to_pool = false;
+            while self.ready_set.contains(&client_id) && queue.deficit > 0 && !queue.queue.is_empty()
+            {
+                let queued = queue.queue.pop_front().expect("queue checked non-empty above");
+                queue.deficit -= 1;
+                queue.last_activity = Instant::now();
+                queue.in_flight_retry = Some((queued.retry.clone(), queued.attempt));
+
+                let mut job = queued.job;
+
+                // there is an active transaction, so we should send it there
+                if let Some(ref sender) = queue.active_txn {
+                    job = match sender.try_send(job) {
+                        Ok(_) => continue,
+                        // the transaction channel was closed before we were notified, we'll send
+                        // to the main queue instead
+                        Err(TrySendError::Disconnected(job)) => {
+                            queue.active_txn.take();
+                            job
+                        }
+                        Err(TrySendError::Full(_)) => {
+                            unreachable!("txn channel should never be full")
+                        }
+                    };
+                }
+
+                // submit job to the main queue:
+                self.worker_pool_sender
+                    .send(job)
+                    .expect("worker pool crashed");
+                dispatched_to_pool = true;
+                break;
             }
 
-            // submit job to the main queue:
-            self.worker_pool_sender
-                .send(job)
-                .expect("worker pool crashed");
+            if dispatched_to_pool {
+                not_ready.push(client_id);
+            }
 
             if queue.queue.is_empty() {
                 not_waiting.push(client_id);
                 if queue.should_close {
                     self.queues.remove(&client_id);
                 }
+            } else {
+                self.active_ring.push_back(client_id);
             }
         }
 
@@ -139,6 +369,7 @@ impl Scheduler {
 
         for e in &not_waiting {
             self.has_work_set.remove(e);
+            self.active_ring.retain(|id| id != e);
         }
     }
 
@@ -147,21 +378,114 @@ impl Scheduler {
         match update {
             UpdateStateMessage::Ready(e) => {
                 self.ready_set.insert(e);
+                if let Some(queue) = self.queues.get_mut(&e) {
+                    queue.last_activity = Instant::now();
+                }
             }
             UpdateStateMessage::TxnBegin(e, sender) => {
                 if let Some(queue) = self.queues.get_mut(&e) {
                     assert!(queue.active_txn.is_none());
                     queue.active_txn.replace(sender);
+                    queue.last_activity = Instant::now();
                 }
             }
             UpdateStateMessage::TxnEnded(e) => {
                 if let Some(queue) = self.queues.get_mut(&e) {
                     // it's ok if the txn was already removed
                     queue.active_txn.take();
+                    queue.last_activity = Instant::now();
                     self.ready_set.insert(e);
                 }
             }
+            UpdateStateMessage::Report(reply) => {
+                // the caller may have stopped waiting for the report; that's not our problem.
+                let _ = reply.send(self.status_snapshot());
+            }
+            UpdateStateMessage::Failed(client_id, job, kind) => {
+                self.handle_failed_job(client_id, job, kind);
+            }
+            UpdateStateMessage::Requeue(client_id, queued) => match self.queues.get_mut(&client_id) {
+                Some(queue) => {
+                    queue.queue.push_front(queued);
+                    queue.last_activity = Instant::now();
+                    self.has_work_set.insert(client_id);
+                    self.ready_set.insert(client_id);
+                }
+                // the client's queue was already torn down (e.g. it disconnected and drained
+                // while this retry was in flight); there's nowhere left to requeue into, so
+                // dead-letter the job directly instead of silently dropping it.
+                None => Self::dead_letter(queued.job, queued.attempt, "client queue no longer exists"),
+            },
+        }
+    }
+
+    /// Decide whether a failed job should be retried with backoff or sent to the dead letter
+    /// path, based on the retry bookkeeping recorded for the client's in-flight job.
+    fn handle_failed_job(&mut self, client_id: ClientId, job: Job, kind: FailureKind) {
+        let retry = self
+            .queues
+            .get_mut(&client_id)
+            .and_then(|queue| queue.in_flight_retry.take())
+            .map(|(retry, attempt)| (retry, attempt + 1))
+            .unwrap_or((RetryPolicy::default(), 1));
+        let (retry, attempt) = retry;
+
+        // the client's "in-flight job" slot is now free, regardless of what we do with it.
+        self.ready_set.insert(client_id);
+
+        if attempt >= retry.max_attempts {
+            Self::dead_letter(job, attempt, kind);
+            return;
         }
+
+        let delay = retry.backoff(attempt);
+        let resend = self.update_state_sender.clone();
+        tokio::spawn(async move {
+            tokio::time::sleep(delay).await;
+            // if the scheduler has since shut down there's nothing more to do.
+            let _ = resend.send(UpdateStateMessage::Requeue(
+                client_id,
+                QueuedJob { job, retry, attempt },
+            ));
+        });
+    }
+
+    /// Answers a job's `Responder` with a "giving up" error. The single exit point for dead
+    /// letters, called both when retries are exhausted and when a retry can't be requeued
+    /// because its client's queue no longer exists, so neither path depends on `self.queues`
+    /// still holding an entry for the client.
+    fn dead_letter(job: Job, attempt: u32, reason: impl fmt::Display) {
+        job.responder.respond(Message::Error(format!(
+            "job failed after {attempt} attempt(s), giving up: {reason}"
+        )));
+    }
+
+    /// Snapshot the current state of every known client queue.
+    fn status_snapshot(&self) -> Vec<QueueStatus> {
+        self.queues
+            .iter()
+            .map(|(&client_id, queue)| {
+                let in_flight = !self.ready_set.contains(&client_id);
+                let state = if queue.should_close && queue.queue.is_empty() {
+                    WorkerState::Done
+                } else if in_flight {
+                    WorkerState::Busy
+                } else if queue.queue.is_empty() {
+                    WorkerState::Idle
+                } else {
+                    WorkerState::Throttled
+                };
+
+                QueueStatus {
+                    client_id,
+                    state,
+                    queued_len: queue.queue.len(),
+                    in_flight,
+                    has_active_txn: queue.active_txn.is_some(),
+                    last_activity: queue.last_activity,
+                }
+            })
+            .collect()
     }
 
     /// Update queues with new incoming tasks from server.
@@ -173,6 +497,14 @@ impl Scheduler {
                     .get_mut(&msg.client_id)
                     .map(|q| q.should_close = true);
             }
+            Action::SetWeight(weight) => {
+                let queue = self.queues.entry(msg.client_id).or_insert_with(|| {
+                    // This is the first time we see this client, so it's ready by default
+                    self.ready_set.insert(msg.client_id);
+                    Default::default()
+                });
+                queue.weight = weight.max(1);
+            }
             Action::Execute(statements) => {
                 let job = Job {
                     scheduler_sender: self.update_state_sender.clone(),
@@ -181,15 +513,17 @@ impl Scheduler {
                     responder: msg.responder,
                 };
 
-                self.queues
-                    .entry(msg.client_id)
-                    .or_insert_with(|| {
-                        // This is the first time we see this client, so it's ready by default
-                        self.ready_set.insert(msg.client_id);
-                        Default::default()
-                    })
-                    .queue
-                    .push_back(job);
+                let queue = self.queues.entry(msg.client_id).or_insert_with(|| {
+                    // This is the first time we see this client, so it's ready by default
+                    self.ready_set.insert(msg.client_id);
+                    Default::default()
+                });
+                queue.queue.push_back(QueuedJob {
+                    job,
+                    retry: RetryPolicy::default(),
+                    attempt: 0,
+                });
+                queue.last_activity = Instant::now();
 
                 self.has_work_set.insert(msg.client_id);
             }
@@ -198,6 +532,13 @@ impl Scheduler {
 
     pub async fn start(mut self) {
         let mut should_exit = false;
+        // becomes true once `shutdown_handle`'s sender flips to `true`; once set we stop
+        // waiting on `must_exit.changed()` since a `watch` only reports the *next* change.
+        let mut shutting_down = *self.must_exit.borrow();
+        if shutting_down {
+            self.begin_shutdown();
+        }
+
         loop {
             tokio::select! {
                 msg = self.update_state_receiver.recv() => {
@@ -208,17 +549,23 @@ impl Scheduler {
                         None => unreachable!("Scheduler still owns a sender"),
                     }
                 },
-                msg = self.job_receiver.recv(), if !should_exit => {
+                msg = self.job_receiver.recv(), if !should_exit && !shutting_down => {
                     match msg {
                         Some(msg) => self.update_queues(msg),
                         None => should_exit = true,
                     }
+                },
+                Ok(()) = self.must_exit.changed(), if !shutting_down => {
+                    if *self.must_exit.borrow() {
+                        shutting_down = true;
+                        self.begin_shutdown();
+                    }
                 }
             }
 
             self.schedule_work();
 
-            if should_exit
+            if (should_exit || shutting_down)
                 // no queue has work left
                 && self.has_work_set.is_empty()
                 // no queue has inflight work
@@ -228,6 +575,25 @@ impl Scheduler {
             }
         }
     }
+
+    /// Stop accepting new jobs and mark every known client queue for removal once it drains.
+    /// In [`ShutdownMode::Abort`], also cancels every job still sitting in a queue, replying
+    /// to its `Responder` with a cancellation error instead of running it.
+    fn begin_shutdown(&mut self) {
+        let abort = self.shutdown_mode == ShutdownMode::Abort;
+        for queue in self.queues.values_mut() {
+            queue.should_close = true;
+            if abort {
+                for queued in queue.queue.drain(..) {
+                    queued
+                        .job
+                        .responder
+                        .respond(Message::Error("scheduler is shutting down".into()));
+                }
+                queue.deficit = 0;
+            }
+        }
+    }
 }
 
 #[cfg(test)]
@@ -239,8 +605,6 @@ mod test {
     use rand::{thread_rng, Rng};
     use std::collections::hash_map::Entry;
 
-    use crate::messages::Message;
-
     use super::*;
 
     struct MockResponder;
@@ -294,6 +658,217 @@ mod test {
         assert_eq!(job.statements.stmts, "SELECT * FROM test2;");
     }
 
+    #[tokio::test]
+    async fn status_report_reflects_queue_state() {
+        let (job_sender, job_receiver) = tokio::sync::mpsc::unbounded_channel();
+        let (pool_sender, pool_receiver) = crossbeam::channel::unbounded();
+        let scheduler = Scheduler::new(pool_sender, job_receiver).unwrap();
+        let update_state_sender = scheduler.update_state_sender();
+
+        tokio::spawn(scheduler.start());
+
+        job_sender
+            .send(ServerMessage {
+                client_id: 0,
+                action: Action::Execute(Statements::parse("SELECT * FROM test;".into()).unwrap()),
+                responder: Box::new(MockResponder),
+            })
+            .unwrap();
+        job_sender
+            .send(ServerMessage {
+                client_id: 0,
+                action: Action::Execute(Statements::parse("SELECT * FROM test2;".into()).unwrap()),
+                responder: Box::new(MockResponder),
+            })
+            .unwrap();
+
+        tokio::time::sleep(Duration::from_millis(5)).await;
+
+        let (reply_tx, reply_rx) = tokio::sync::oneshot::channel();
+        update_state_sender
+            .send(UpdateStateMessage::Report(reply_tx))
+            .unwrap();
+        let report = reply_rx.await.unwrap();
+
+        assert_eq!(report.len(), 1);
+        let status = &report[0];
+        assert_eq!(status.client_id, 0);
+        assert_eq!(status.state, WorkerState::Busy);
+        assert!(status.in_flight);
+        assert_eq!(status.queued_len, 1);
+
+        let job = pool_receiver.try_recv().unwrap();
+        job.scheduler_sender
+            .send(UpdateStateMessage::Ready(0))
+            .unwrap();
+
+        tokio::time::sleep(Duration::from_millis(10)).await;
+
+        let (reply_tx, reply_rx) = tokio::sync::oneshot::channel();
+        update_state_sender
+            .send(UpdateStateMessage::Report(reply_tx))
+            .unwrap();
+        let report = reply_rx.await.unwrap();
+
+        let status = &report[0];
+        assert_eq!(status.queued_len, 0);
+        assert_eq!(status.state, WorkerState::Busy);
+    }
+
+    #[tokio::test]
+    async fn failed_job_retries_with_backoff_then_dead_letters() {
+        use std::sync::atomic::{AtomicBool, Ordering};
+        use std::sync::Arc;
+
+        struct CapturingResponder(Arc<AtomicBool>);
+
+        impl Responder for CapturingResponder {
+            fn respond(&self, _: Message) {
+                self.0.store(true, Ordering::SeqCst);
+            }
+        }
+
+        let (job_sender, job_receiver) = tokio::sync::mpsc::unbounded_channel();
+        let (pool_sender, pool_receiver) = crossbeam::channel::unbounded();
+        let scheduler = Scheduler::new(pool_sender, job_receiver).unwrap();
+        let update_state_sender = scheduler.update_state_sender();
+
+        tokio::spawn(scheduler.start());
+
+        let responded = Arc::new(AtomicBool::new(false));
+        job_sender
+            .send(ServerMessage {
+                client_id: 0,
+                action: Action::Execute(Statements::parse("SELECT * FROM test;".into()).unwrap()),
+                responder: Box::new(CapturingResponder(responded.clone())),
+            })
+            .unwrap();
+
+        tokio::time::sleep(Duration::from_millis(5)).await;
+        let mut job = pool_receiver.try_recv().unwrap();
+
+        // `RetryPolicy::default()` allows 3 attempts: the first two failures should be
+        // retried (with growing backoff) and redispatched, the third should dead-letter.
+        for _ in 0..2 {
+            update_state_sender
+                .send(UpdateStateMessage::Failed(0, job, FailureKind::Busy))
+                .unwrap();
+            tokio::time::sleep(Duration::from_millis(300)).await;
+            job = pool_receiver.try_recv().expect("job should have been retried");
+            assert!(!responded.load(Ordering::SeqCst));
+        }
+
+        update_state_sender
+            .send(UpdateStateMessage::Failed(0, job, FailureKind::Busy))
+            .unwrap();
+        tokio::time::sleep(Duration::from_millis(20)).await;
+
+        assert_eq!(
+            pool_receiver.try_recv().unwrap_err(),
+            TryRecvError::Empty,
+            "exhausted job should not be retried again"
+        );
+        assert!(responded.load(Ordering::SeqCst));
+    }
+
+    #[tokio::test]
+    async fn requeue_dead_letters_when_client_queue_gone() {
+        use std::sync::atomic::{AtomicBool, Ordering};
+        use std::sync::Arc;
+
+        struct CapturingResponder(Arc<AtomicBool>);
+
+        impl Responder for CapturingResponder {
+            fn respond(&self, _: Message) {
+                self.0.store(true, Ordering::SeqCst);
+            }
+        }
+
+        let (_job_sender, job_receiver) = tokio::sync::mpsc::unbounded_channel();
+        let (pool_sender, _pool_receiver) = crossbeam::channel::unbounded();
+        let mut scheduler = Scheduler::new(pool_sender, job_receiver).unwrap();
+
+        let responded = Arc::new(AtomicBool::new(false));
+        let job = Job {
+            scheduler_sender: scheduler.update_state_sender(),
+            statements: Statements::parse("SELECT * FROM test;".into()).unwrap(),
+            client_id: 0,
+            responder: Box::new(CapturingResponder(responded.clone())),
+        };
+
+        // client 0 has no entry in `queues` at all, as if it disconnected and its queue was
+        // torn down while this retry's backoff timer was still running. The requeue should
+        // dead-letter the job instead of silently dropping it.
+        scheduler.update_queue_status(UpdateStateMessage::Requeue(
+            0,
+            QueuedJob {
+                job,
+                retry: RetryPolicy::default(),
+                attempt: 2,
+            },
+        ));
+
+        assert!(responded.load(Ordering::SeqCst));
+    }
+
+    #[tokio::test]
+    async fn abort_shutdown_cancels_queued_jobs() {
+        use std::sync::atomic::{AtomicBool, Ordering};
+        use std::sync::Arc;
+
+        struct CapturingResponder(Arc<AtomicBool>);
+
+        impl Responder for CapturingResponder {
+            fn respond(&self, _: Message) {
+                self.0.store(true, Ordering::SeqCst);
+            }
+        }
+
+        let (job_sender, job_receiver) = tokio::sync::mpsc::unbounded_channel();
+        let (pool_sender, pool_receiver) = crossbeam::channel::unbounded();
+        let scheduler =
+            Scheduler::new(pool_sender, job_receiver).unwrap().with_shutdown_mode(ShutdownMode::Abort);
+        let shutdown = scheduler.shutdown_handle();
+
+        let handle = tokio::spawn(scheduler.start());
+
+        // client 0's first statement occupies its one in-flight slot; the second one is left
+        // sitting in the queue and should be the one that gets cancelled.
+        job_sender
+            .send(ServerMessage {
+                client_id: 0,
+                action: Action::Execute(Statements::parse("SELECT * FROM test;".into()).unwrap()),
+                responder: Box::new(MockResponder),
+            })
+            .unwrap();
+        let cancelled = Arc::new(AtomicBool::new(false));
+        job_sender
+            .send(ServerMessage {
+                client_id: 0,
+                action: Action::Execute(Statements::parse("SELECT * FROM test2;".into()).unwrap()),
+                responder: Box::new(CapturingResponder(cancelled.clone())),
+            })
+            .unwrap();
+
+        tokio::time::sleep(Duration::from_millis(5)).await;
+        let job = pool_receiver.try_recv().unwrap();
+
+        shutdown.send(true).unwrap();
+        tokio::time::sleep(Duration::from_millis(5)).await;
+
+        assert!(cancelled.load(Ordering::SeqCst));
+
+        // finish the in-flight job so the scheduler can observe every queue has drained.
+        job.scheduler_sender
+            .send(UpdateStateMessage::Ready(0))
+            .unwrap();
+
+        tokio::time::timeout(Duration::from_millis(100), handle)
+            .await
+            .expect("scheduler should shut down once queues drain")
+            .unwrap();
+    }
+
     #[tokio::test]
     async fn different_clients_processed_concurrently() {
         let (job_sender, job_receiver) = tokio::sync::mpsc::unbounded_channel();
@@ -339,6 +914,66 @@ mod test {
         assert_eq!(pool_receiver.try_recv().unwrap_err(), TryRecvError::Empty);
     }
 
+    #[tokio::test]
+    async fn set_weight_gives_higher_weight_client_a_bigger_dispatch_share() {
+        const JOBS_PER_CLIENT: usize = 100;
+        const SAMPLE: usize = 40;
+
+        let (job_sender, job_receiver) = tokio::sync::mpsc::unbounded_channel();
+        let (pool_sender, pool_receiver) = crossbeam::channel::unbounded();
+        let scheduler = Scheduler::new(pool_sender, job_receiver).unwrap();
+
+        tokio::spawn(scheduler.start());
+
+        for i in 0..JOBS_PER_CLIENT {
+            for client_id in [0usize, 1] {
+                job_sender
+                    .send(ServerMessage {
+                        client_id,
+                        action: Action::Execute(
+                            Statements::parse(format!("SELECT * FROM \"{i}\"")).unwrap(),
+                        ),
+                        responder: Box::new(MockResponder),
+                    })
+                    .unwrap();
+            }
+        }
+
+        // client 0 gets three times client 1's weight, so it should claim a bigger share of
+        // the dispatch order while both queues are still flooded.
+        job_sender
+            .send(ServerMessage {
+                client_id: 0,
+                action: Action::SetWeight(3),
+                responder: Box::new(MockResponder),
+            })
+            .unwrap();
+
+        let mut dispatched = [0usize; 2];
+        while dispatched[0] + dispatched[1] < SAMPLE {
+            // give the scheduler task a chance to run between polls instead of blocking this
+            // test's only thread on a crossbeam recv.
+            tokio::time::sleep(Duration::from_millis(5)).await;
+
+            let Ok(job) = pool_receiver.try_recv() else {
+                continue;
+            };
+            dispatched[job.client_id] += 1;
+            job.scheduler_sender
+                .send(UpdateStateMessage::Ready(job.client_id))
+                .unwrap();
+        }
+
+        // ideally a 3:1 split, but leave slack for DRR quantum rounding instead of pinning an
+        // exact ratio.
+        assert!(
+            dispatched[0] > dispatched[1] * 2,
+            "client 0 (weight 3) got {} of the first {SAMPLE} dispatches vs client 1's {}",
+            dispatched[0],
+            dispatched[1]
+        );
+    }
+
     proptest! {
         /// This test's goal is to schedule random jobs and make sure that:
         /// - all jobs get processed
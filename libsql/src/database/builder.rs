@@ -46,10 +46,12 @@ impl Builder<()> {
                         auth_token,
                         connector: None,
                         version: None,
+                        pool: None,
                     },
                     encryption_key: None,
                     read_your_writes: false,
-                    periodic_sync: None
+                    periodic_sync: None,
+                    reconnect_strategy: None,
                 },
             }
         }
@@ -76,6 +78,7 @@ impl Builder<()> {
                     auth_token,
                     connector: None,
                     version: None,
+                    pool: None,
                 },
             }
         }
@@ -89,6 +92,32 @@ cfg_replication_or_remote! {
         auth_token: String,
         connector: Option<crate::util::ConnectorService>,
         version: Option<String>,
+        pool: Option<PoolConfig>,
+    }
+
+    /// Settings for the connection pool configured via `Builder::pool_max_size` and friends.
+    /// Left unset (the default), every request dials a fresh connection exactly as before.
+    /// Configuring one bounds how many connections may be dialed or held open at once
+    /// (`max_size`, queuing callers beyond that for up to `acquire_timeout`), keeps a reservoir
+    /// of pre-warmed idle connections ready to hand out (`min_idle`), and closes idle
+    /// connections that have sat unused for longer than `idle_timeout`.
+    #[derive(Debug, Clone, Copy)]
+    pub struct PoolConfig {
+        max_size: usize,
+        min_idle: usize,
+        idle_timeout: std::time::Duration,
+        acquire_timeout: std::time::Duration,
+    }
+
+    impl Default for PoolConfig {
+        fn default() -> Self {
+            Self {
+                max_size: 1,
+                min_idle: 0,
+                idle_timeout: std::time::Duration::from_secs(10 * 60),
+                acquire_timeout: std::time::Duration::from_secs(30),
+            }
+        }
     }
 }
 
@@ -141,6 +170,52 @@ cfg_replication! {
         encryption_key: Option<bytes::Bytes>,
         read_your_writes: bool,
         periodic_sync: Option<std::time::Duration>,
+        reconnect_strategy: Option<ReconnectStrategy>,
+    }
+
+    /// Controls how the `periodic_sync` background task recovers from a failed sync round
+    /// against the remote primary: exponential backoff with jitter between attempts, capped
+    /// at `max_delay`, giving up once a round has failed `max_retries + 1` times in a row (the
+    /// first try plus `max_retries` retries). Left unset,
+    /// `periodic_sync` uses [`ReconnectStrategy::default`].
+    #[derive(Debug, Clone, Copy)]
+    pub struct ReconnectStrategy {
+        pub base_delay: std::time::Duration,
+        pub max_delay: std::time::Duration,
+        pub max_retries: u32,
+    }
+
+    impl Default for ReconnectStrategy {
+        fn default() -> Self {
+            Self {
+                base_delay: std::time::Duration::from_millis(100),
+                max_delay: std::time::Duration::from_secs(10),
+                max_retries: 5,
+            }
+        }
+    }
+
+    impl ReconnectStrategy {
+        /// Backoff delay for the given (0-indexed) attempt: `min(base_delay * 2^attempt,
+        /// max_delay)`, jittered by up to +/-20% (sourced from the clock, not a dedicated RNG)
+        /// so a fleet of replicas reconnecting at the same moment doesn't retry in lockstep.
+        pub(crate) fn backoff(&self, attempt: u32) -> std::time::Duration {
+            let factor = 1u32.checked_shl(attempt).unwrap_or(u32::MAX);
+            let delay = self.base_delay.saturating_mul(factor).min(self.max_delay);
+
+            let jitter_range = (delay.as_millis() as u64 / 5).max(1);
+            let nanos = std::time::SystemTime::now()
+                .duration_since(std::time::UNIX_EPOCH)
+                .map(|d| d.subsec_nanos())
+                .unwrap_or(0) as u64;
+            let signed_jitter = (nanos % (2 * jitter_range)) as i64 - jitter_range as i64;
+
+            if signed_jitter >= 0 {
+                delay.saturating_add(std::time::Duration::from_millis(signed_jitter as u64))
+            } else {
+                delay.saturating_sub(std::time::Duration::from_millis((-signed_jitter) as u64))
+            }
+        }
     }
 
     /// Local replica configuration type in [`Builder`].
@@ -188,12 +263,49 @@ cfg_replication! {
             self
         }
 
+        /// Configure how the `periodic_sync` background task retries a failed sync round. Has
+        /// no effect unless `periodic_sync` is also set.
+        pub fn reconnect_strategy(mut self, strategy: ReconnectStrategy) -> Builder<RemoteReplica> {
+            self.inner.reconnect_strategy = Some(strategy);
+            self
+        }
+
         #[doc(hidden)]
         pub fn version(mut self, version: String) -> Builder<RemoteReplica> {
             self.inner.remote = self.inner.remote.version(version);
             self
         }
 
+        /// Cap the number of connections kept outstanding to the remote primary at once.
+        /// Callers beyond the cap wait for a permit, up to `pool_acquire_timeout`. If another
+        /// pool setting is configured without this one, the cap is `1`; with no pool setting
+        /// at all, connections are neither pooled nor capped.
+        pub fn pool_max_size(mut self, max_size: usize) -> Builder<RemoteReplica> {
+            self.inner.remote = self.inner.remote.pool_max_size(max_size);
+            self
+        }
+
+        /// Keep at least this many idle connections pre-warmed and ready to hand out, topped
+        /// up in the background as connections are checked out. Defaults to `0`, i.e. only
+        /// dial on demand.
+        pub fn pool_min_idle(mut self, min_idle: usize) -> Builder<RemoteReplica> {
+            self.inner.remote = self.inner.remote.pool_min_idle(min_idle);
+            self
+        }
+
+        /// Close a pooled connection if it has sat idle for longer than this. Defaults to 10
+        /// minutes.
+        pub fn pool_idle_timeout(mut self, timeout: std::time::Duration) -> Builder<RemoteReplica> {
+            self.inner.remote = self.inner.remote.pool_idle_timeout(timeout);
+            self
+        }
+
+        /// How long to wait for a pooled connection to become available before giving up.
+        pub fn pool_acquire_timeout(mut self, timeout: std::time::Duration) -> Builder<RemoteReplica> {
+            self.inner.remote = self.inner.remote.pool_acquire_timeout(timeout);
+            self
+        }
+
         /// Build the remote embedded replica database.
         pub async fn build(self) -> Result<Database> {
             let RemoteReplica {
@@ -204,10 +316,12 @@ cfg_replication! {
                         auth_token,
                         connector,
                         version,
+                        pool,
                     },
                 encryption_key,
                 read_your_writes,
-                periodic_sync
+                periodic_sync,
+                reconnect_strategy,
             } = self.inner;
 
             let connector = if let Some(connector) = connector {
@@ -222,6 +336,7 @@ cfg_replication! {
 
                 crate::util::ConnectorService::new(svc)
             };
+            let connector = pooled(connector, pool);
 
             let path = path.to_str().ok_or(crate::Error::InvalidUTF8Path)?.to_owned();
 
@@ -233,7 +348,8 @@ cfg_replication! {
                 version,
                 read_your_writes,
                 encryption_key.clone(),
-                periodic_sync
+                periodic_sync,
+                reconnect_strategy.unwrap_or_default(),
             )
             .await?;
 
@@ -266,6 +382,7 @@ cfg_replication! {
                 auth_token,
                 connector,
                 version,
+                pool,
             }) = remote
             {
                 let connector = if let Some(connector) = connector {
@@ -280,6 +397,7 @@ cfg_replication! {
 
                     crate::util::ConnectorService::new(svc)
                 };
+                let connector = pooled(connector, pool);
 
                 crate::local::Database::open_local_sync_remote_writes(
                     connector,
@@ -305,6 +423,11 @@ cfg_replication! {
 cfg_remote! {
     impl Builder<Remote> {
         /// Provide a custom http connector that will be used to create http connections.
+        ///
+        /// Not available on `wasm32`: there, requests are driven through the host's `fetch`
+        /// binding instead of a `tower::Service<http::Uri>`, so there is no raw socket
+        /// connector to swap out.
+        #[cfg(not(target_arch = "wasm32"))]
         pub fn connector<C>(mut self, connector: C) -> Builder<Remote>
         where
             C: tower::Service<http::Uri> + Send + Clone + Sync + 'static,
@@ -322,6 +445,49 @@ cfg_remote! {
             self
         }
 
+        /// Cap the number of connections kept outstanding to the remote database at once.
+        /// Callers beyond the cap wait for a permit, up to `pool_acquire_timeout`. If another
+        /// pool setting is configured without this one, the cap is `1`; with no pool setting
+        /// at all, connections are neither pooled nor capped.
+        ///
+        /// Not available on `wasm32`: there, requests are driven through the host's `fetch`
+        /// binding, which pools connections itself.
+        #[cfg(not(target_arch = "wasm32"))]
+        pub fn pool_max_size(mut self, max_size: usize) -> Builder<Remote> {
+            self.inner = self.inner.pool_max_size(max_size);
+            self
+        }
+
+        /// Keep at least this many idle connections pre-warmed and ready to hand out, topped
+        /// up in the background as connections are checked out. Defaults to `0`, i.e. only
+        /// dial on demand.
+        ///
+        /// Not available on `wasm32`: there, requests are driven through the host's `fetch`
+        /// binding, which pools connections itself.
+        #[cfg(not(target_arch = "wasm32"))]
+        pub fn pool_min_idle(mut self, min_idle: usize) -> Builder<Remote> {
+            self.inner = self.inner.pool_min_idle(min_idle);
+            self
+        }
+
+        /// Close a pooled connection if it has sat idle for longer than this. Defaults to 10
+        /// minutes.
+        ///
+        /// Not available on `wasm32`: there, requests are driven through the host's `fetch`
+        /// binding, which pools connections itself.
+        #[cfg(not(target_arch = "wasm32"))]
+        pub fn pool_idle_timeout(mut self, timeout: std::time::Duration) -> Builder<Remote> {
+            self.inner = self.inner.pool_idle_timeout(timeout);
+            self
+        }
+
+        /// How long to wait for a pooled connection to become available before giving up.
+        #[cfg(not(target_arch = "wasm32"))]
+        pub fn pool_acquire_timeout(mut self, timeout: std::time::Duration) -> Builder<Remote> {
+            self.inner = self.inner.pool_acquire_timeout(timeout);
+            self
+        }
+
         /// Build the remote database client.
         pub async fn build(self) -> Result<Database> {
             let Remote {
@@ -329,20 +495,14 @@ cfg_remote! {
                 auth_token,
                 connector,
                 version,
+                pool,
             } = self.inner;
 
-            let connector = if let Some(connector) = connector {
-                connector
-            } else {
-                let https = super::connector();
-                use tower::ServiceExt;
-
-                let svc = https
-                    .map_err(|e| e.into())
-                    .map_response(|s| Box::new(s) as Box<dyn crate::util::Socket>);
-
-                crate::util::ConnectorService::new(svc)
-            };
+            let connector = Self::connector_or_default(connector);
+            #[cfg(not(target_arch = "wasm32"))]
+            let connector = pooled(connector, pool);
+            #[cfg(target_arch = "wasm32")]
+            let _ = pool;
 
             Ok(Database {
                 db_type: DbType::Remote {
@@ -353,6 +513,31 @@ cfg_remote! {
                 },
             })
         }
+
+        /// Falls back to the platform-default connector (native TCP/TLS socket, or the
+        /// `wasm32` `fetch`-backed transport) when the caller didn't supply one of their own.
+        #[cfg(not(target_arch = "wasm32"))]
+        fn connector_or_default(
+            connector: Option<crate::util::ConnectorService>,
+        ) -> crate::util::ConnectorService {
+            connector.unwrap_or_else(|| {
+                let https = super::connector();
+                use tower::ServiceExt;
+
+                let svc = https
+                    .map_err(|e| e.into())
+                    .map_response(|s| Box::new(s) as Box<dyn crate::util::Socket>);
+
+                crate::util::ConnectorService::new(svc)
+            })
+        }
+
+        #[cfg(target_arch = "wasm32")]
+        fn connector_or_default(
+            connector: Option<crate::util::ConnectorService>,
+        ) -> crate::util::ConnectorService {
+            connector.unwrap_or_default()
+        }
     }
 }
 
@@ -381,5 +566,300 @@ cfg_replication_or_remote! {
             self.version = Some(version);
             self
         }
+
+        fn pool_max_size(mut self, max_size: usize) -> Remote {
+            self.pool.get_or_insert_with(PoolConfig::default).max_size = max_size;
+            self
+        }
+
+        fn pool_min_idle(mut self, min_idle: usize) -> Remote {
+            self.pool.get_or_insert_with(PoolConfig::default).min_idle = min_idle;
+            self
+        }
+
+        fn pool_idle_timeout(mut self, timeout: std::time::Duration) -> Remote {
+            self.pool.get_or_insert_with(PoolConfig::default).idle_timeout = timeout;
+            self
+        }
+
+        fn pool_acquire_timeout(mut self, timeout: std::time::Duration) -> Remote {
+            self.pool.get_or_insert_with(PoolConfig::default).acquire_timeout = timeout;
+            self
+        }
+    }
+
+    /// Wraps `connector` with a warm-connection pool: at most `pool.max_size` connections are
+    /// dialed or held open at once (queuing callers beyond that up to `pool.acquire_timeout`),
+    /// connections returned to the pool are handed back out to a later caller instead of being
+    /// re-dialed, `pool.min_idle` of them are kept pre-warmed per target, and any that have sat
+    /// idle longer than `pool.idle_timeout` are closed. A `None` pool (the default) returns
+    /// `connector` unchanged, preserving the pre-existing dial-per-request behavior.
+    ///
+    /// Note: this pools raw connections per remote URL; it does not distinguish read traffic
+    /// from write traffic; a `Builder` only ever has one `connector` to hand to the database it
+    /// builds, and that database decides internally which queries are reads and which are
+    /// writes. Splitting the pool along that line would need to happen where that routing
+    /// decision is made, not here.
+    fn pooled(
+        connector: crate::util::ConnectorService,
+        pool: Option<PoolConfig>,
+    ) -> crate::util::ConnectorService {
+        match pool {
+            Some(config) => {
+                crate::util::ConnectorService::new(PooledConnector::new(connector, config))
+            }
+            None => connector,
+        }
+    }
+
+    /// A connection that has been returned to the pool, waiting to be handed back out.
+    struct IdleSocket<T> {
+        socket: T,
+        permit: tokio::sync::OwnedSemaphorePermit,
+        returned_at: std::time::Instant,
+    }
+
+    /// A [`tower::Service`] middleware implementing a warm-connection pool on top of an inner
+    /// connector: `call` first tries to hand out an idle connection for the same target
+    /// (discarding any that have gone stale or whose peer has already hung up along the way),
+    /// and only dials through to `inner` on a pool miss. At most `max_size` connections (idle
+    /// or checked out) exist per pool at once, gated by `permits`; callers beyond that wait up
+    /// to `acquire_timeout`. Connections are indexed by `http::Uri::to_string` since a single
+    /// pool generally only ever sees one target, but keying by target keeps this correct if
+    /// that ever changes.
+    #[derive(Clone)]
+    struct PooledConnector<S>
+    where
+        S: tower::Service<http::Uri>,
+    {
+        inner: S,
+        permits: std::sync::Arc<tokio::sync::Semaphore>,
+        acquire_timeout: std::time::Duration,
+        min_idle: usize,
+        idle_timeout: std::time::Duration,
+        idle: std::sync::Arc<
+            std::sync::Mutex<std::collections::HashMap<String, std::collections::VecDeque<IdleSocket<S::Response>>>>,
+        >,
+    }
+
+    impl<S> PooledConnector<S>
+    where
+        S: tower::Service<http::Uri>,
+    {
+        fn new(inner: S, config: PoolConfig) -> Self {
+            Self {
+                inner,
+                permits: std::sync::Arc::new(tokio::sync::Semaphore::new(config.max_size)),
+                acquire_timeout: config.acquire_timeout,
+                min_idle: config.min_idle,
+                idle_timeout: config.idle_timeout,
+                idle: Default::default(),
+            }
+        }
+    }
+
+    /// A dialed socket tied to the pool permit that admitted it. On drop, it's handed back to
+    /// `idle` for reuse rather than torn down, keeping its permit reserved the whole time: a
+    /// permit represents "this connection exists" (idle or in use), not "this connection is
+    /// currently in use".
+    struct PooledSocket<T> {
+        inner: Option<T>,
+        permit: Option<tokio::sync::OwnedSemaphorePermit>,
+        idle: std::sync::Arc<std::sync::Mutex<std::collections::HashMap<String, std::collections::VecDeque<IdleSocket<T>>>>>,
+        key: String,
+    }
+
+    impl<T> Drop for PooledSocket<T> {
+        fn drop(&mut self) {
+            if let (Some(socket), Some(permit)) = (self.inner.take(), self.permit.take()) {
+                let mut idle = self.idle.lock().unwrap();
+                idle.entry(self.key.clone()).or_default().push_back(IdleSocket {
+                    socket,
+                    permit,
+                    returned_at: std::time::Instant::now(),
+                });
+            }
+        }
+    }
+
+    impl<T: tokio::io::AsyncRead + Unpin> tokio::io::AsyncRead for PooledSocket<T> {
+        fn poll_read(
+            self: std::pin::Pin<&mut Self>,
+            cx: &mut std::task::Context<'_>,
+            buf: &mut tokio::io::ReadBuf<'_>,
+        ) -> std::task::Poll<std::io::Result<()>> {
+            std::pin::Pin::new(self.get_mut().inner.as_mut().expect("socket taken")).poll_read(cx, buf)
+        }
+    }
+
+    impl<T: tokio::io::AsyncWrite + Unpin> tokio::io::AsyncWrite for PooledSocket<T> {
+        fn poll_write(
+            self: std::pin::Pin<&mut Self>,
+            cx: &mut std::task::Context<'_>,
+            buf: &[u8],
+        ) -> std::task::Poll<std::io::Result<usize>> {
+            std::pin::Pin::new(self.get_mut().inner.as_mut().expect("socket taken")).poll_write(cx, buf)
+        }
+
+        fn poll_flush(
+            self: std::pin::Pin<&mut Self>,
+            cx: &mut std::task::Context<'_>,
+        ) -> std::task::Poll<std::io::Result<()>> {
+            std::pin::Pin::new(self.get_mut().inner.as_mut().expect("socket taken")).poll_flush(cx)
+        }
+
+        fn poll_shutdown(
+            self: std::pin::Pin<&mut Self>,
+            cx: &mut std::task::Context<'_>,
+        ) -> std::task::Poll<std::io::Result<()>> {
+            std::pin::Pin::new(self.get_mut().inner.as_mut().expect("socket taken")).poll_shutdown(cx)
+        }
+    }
+
+    impl<T: crate::util::Socket> crate::util::Socket for PooledSocket<T> {}
+
+    /// Whether `socket` looks unusable for reuse: either its peer has already hung up (a
+    /// zero-byte read means EOF) or it's currently erroring. A pending read — no unsolicited
+    /// bytes waiting, the expected state for an idle keep-alive connection — means it's still
+    /// good, and critically this peek never blocks and never consumes a byte a future real read
+    /// would have wanted: the only paths that return `true` here are ones where the connection
+    /// is about to be discarded anyway.
+    fn is_dead<T: tokio::io::AsyncRead + Unpin>(socket: &mut T) -> bool {
+        let waker = futures::task::noop_waker();
+        let mut cx = std::task::Context::from_waker(&waker);
+        let mut scratch = [0u8; 1];
+        let mut buf = tokio::io::ReadBuf::new(&mut scratch);
+        match std::pin::Pin::new(socket).poll_read(&mut cx, &mut buf) {
+            std::task::Poll::Ready(Ok(())) => buf.filled().is_empty(),
+            std::task::Poll::Ready(Err(_)) => true,
+            std::task::Poll::Pending => false,
+        }
+    }
+
+    /// Tops up the idle reservoir for `key` up to `min_idle`, dialing in the background and
+    /// stopping as soon as either the reservoir is full or the pool has no spare permits left
+    /// to pre-warm with — pre-warming never competes with an in-flight caller for a permit.
+    fn spawn_min_idle_fill<S>(
+        key: String,
+        uri: http::Uri,
+        mut inner: S,
+        permits: std::sync::Arc<tokio::sync::Semaphore>,
+        idle: std::sync::Arc<
+            std::sync::Mutex<std::collections::HashMap<String, std::collections::VecDeque<IdleSocket<S::Response>>>>,
+        >,
+        min_idle: usize,
+    ) where
+        S: tower::Service<http::Uri> + Send + 'static,
+        S::Response: Send + 'static,
+        S::Future: Send + 'static,
+    {
+        if min_idle == 0 {
+            return;
+        }
+
+        tokio::spawn(async move {
+            loop {
+                let current = idle
+                    .lock()
+                    .unwrap()
+                    .get(&key)
+                    .map(std::collections::VecDeque::len)
+                    .unwrap_or(0);
+                if current >= min_idle {
+                    break;
+                }
+
+                let Ok(permit) = permits.clone().try_acquire_owned() else {
+                    break;
+                };
+
+                match inner.call(uri.clone()).await {
+                    Ok(socket) => {
+                        idle.lock().unwrap().entry(key.clone()).or_default().push_back(IdleSocket {
+                            socket,
+                            permit,
+                            returned_at: std::time::Instant::now(),
+                        });
+                    }
+                    Err(_) => break,
+                }
+            }
+        });
+    }
+
+    impl<S> tower::Service<http::Uri> for PooledConnector<S>
+    where
+        S: tower::Service<http::Uri> + Clone + Send + 'static,
+        S::Response: crate::util::Socket + 'static,
+        S::Future: Send + 'static,
+        S::Error: Into<Box<dyn std::error::Error + Send + Sync>> + Send + Sync + 'static,
+    {
+        type Response = PooledSocket<S::Response>;
+        type Error = Box<dyn std::error::Error + Send + Sync>;
+        type Future = std::pin::Pin<
+            Box<dyn std::future::Future<Output = Result<Self::Response, Self::Error>> + Send>,
+        >;
+
+        fn poll_ready(
+            &mut self,
+            cx: &mut std::task::Context<'_>,
+        ) -> std::task::Poll<Result<(), Self::Error>> {
+            self.inner.poll_ready(cx).map_err(Into::into)
+        }
+
+        fn call(&mut self, uri: http::Uri) -> Self::Future {
+            let mut inner = self.inner.clone();
+            let permits = self.permits.clone();
+            let acquire_timeout = self.acquire_timeout;
+            let idle = self.idle.clone();
+            let idle_timeout = self.idle_timeout;
+            let min_idle = self.min_idle;
+            let key = uri.to_string();
+
+            Box::pin(async move {
+                loop {
+                    let candidate = idle.lock().unwrap().get_mut(&key).and_then(std::collections::VecDeque::pop_front);
+                    let Some(mut entry) = candidate else {
+                        break;
+                    };
+
+                    if entry.returned_at.elapsed() >= idle_timeout || is_dead(&mut entry.socket) {
+                        // stale or already hung up; dropping `entry` here releases its permit.
+                        continue;
+                    }
+
+                    spawn_min_idle_fill(
+                        key.clone(),
+                        uri.clone(),
+                        inner.clone(),
+                        permits.clone(),
+                        idle.clone(),
+                        min_idle,
+                    );
+                    return Ok(PooledSocket {
+                        inner: Some(entry.socket),
+                        permit: Some(entry.permit),
+                        idle,
+                        key,
+                    });
+                }
+
+                let permit = tokio::time::timeout(acquire_timeout, permits.clone().acquire_owned())
+                    .await
+                    .map_err(|_| "timed out waiting for a pooled connection")?
+                    .expect("pool semaphore is never closed");
+
+                let socket = inner.call(uri.clone()).await.map_err(Into::into)?;
+
+                spawn_min_idle_fill(key.clone(), uri, inner, permits, idle.clone(), min_idle);
+
+                Ok(PooledSocket {
+                    inner: Some(socket),
+                    permit: Some(permit),
+                    idle,
+                    key,
+                })
+            })
+        }
     }
 }
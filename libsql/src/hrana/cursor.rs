@@ -1,8 +1,9 @@
 // https://github.com/tursodatabase/libsql/blob/main/docs/HRANA_3_SPEC.md#cursor-entries
 
 use crate::hrana::proto::{Batch, Col, Value};
-use crate::hrana::{HttpSend, Result};
-use futures::lock::Mutex;
+use crate::hrana::{HranaError, HttpSend, Result};
+use bytes::{Buf, BytesMut};
+use futures::{Stream, StreamExt};
 use serde::{Deserialize, Serialize};
 use std::pin::Pin;
 use std::sync::Arc;
@@ -58,20 +59,305 @@ pub struct ErrorEntry {
     pub error: String,
 }
 
+/// Pops the next newline-delimited line out of `buf`, if a full line is already buffered.
+fn take_line(buf: &mut BytesMut) -> Option<BytesMut> {
+    let pos = buf.iter().position(|&b| b == b'\n')?;
+    let line = buf.split_to(pos);
+    // drop the newline itself
+    buf.advance(1);
+    Some(line)
+}
+
+/// Parse one line of the cursor body into a [`CursorEntry`], turning `Error`/`StepError`
+/// entries into an `Err` so callers don't need to match on them separately.
+fn parse_entry(line: &[u8]) -> Result<CursorEntry> {
+    let entry: CursorEntry = serde_json::from_slice(line)?;
+    match entry {
+        CursorEntry::Error(ErrorEntry { error }) => Err(HranaError::Api(error)),
+        CursorEntry::StepError(StepErrorEntry { step, error }) => {
+            Err(HranaError::Api(format!("step {step} failed: {error}")))
+        }
+        entry => Ok(entry),
+    }
+}
+
+/// A single result row paired with the column metadata of the step it came from.
+///
+/// Obtained from [`Cursor::next_typed`]; decode it into a concrete type with [`FromRow`]
+/// instead of pulling values out of the untyped `Vec<Value>` by hand.
+#[derive(Debug)]
+pub struct Row<'a> {
+    cols: &'a [Col],
+    values: &'a [Value],
+}
+
+impl<'a> Row<'a> {
+    /// Number of columns in this row.
+    pub fn len(&self) -> usize {
+        self.values.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.values.is_empty()
+    }
+
+    /// The raw value of the column at `idx`, or `None` if `idx` is out of range.
+    pub fn value(&self, idx: usize) -> Option<&Value> {
+        self.values.get(idx)
+    }
+
+    /// The raw value of the column named `name`, or `None` if there is no such column.
+    pub fn value_by_name(&self, name: &str) -> Option<&Value> {
+        let idx = self
+            .cols
+            .iter()
+            .position(|col| col.name.as_deref() == Some(name))?;
+        self.values.get(idx)
+    }
+}
+
+/// Decodes a single column's [`Value`] into a Rust type.
+///
+/// This is the per-column counterpart of [`FromRow`]: tuple impls of `FromRow` decode each
+/// element by position via `FromValue`, and hand-written `FromRow` impls for structs
+/// typically call it once per field via [`Row::value_by_name`].
+pub trait FromValue: Sized {
+    fn from_value(value: Option<&Value>) -> Result<Self>;
+}
+
+fn column_type_error(expected: &str, got: Option<&Value>) -> HranaError {
+    match got {
+        Some(value) => HranaError::Api(format!("expected a {expected} column, got {value:?}")),
+        None => HranaError::Api(format!("expected a {expected} column, got no column")),
+    }
+}
+
+impl FromValue for i64 {
+    fn from_value(value: Option<&Value>) -> Result<Self> {
+        match value {
+            Some(Value::Integer { value }) => Ok(*value),
+            other => Err(column_type_error("integer", other)),
+        }
+    }
+}
+
+impl FromValue for f64 {
+    fn from_value(value: Option<&Value>) -> Result<Self> {
+        match value {
+            Some(Value::Float { value }) => Ok(*value),
+            other => Err(column_type_error("float", other)),
+        }
+    }
+}
+
+impl FromValue for String {
+    fn from_value(value: Option<&Value>) -> Result<Self> {
+        match value {
+            Some(Value::Text { value }) => Ok(value.clone()),
+            other => Err(column_type_error("text", other)),
+        }
+    }
+}
+
+impl FromValue for Vec<u8> {
+    fn from_value(value: Option<&Value>) -> Result<Self> {
+        match value {
+            Some(Value::Blob { value }) => Ok(value.to_vec()),
+            other => Err(column_type_error("blob", other)),
+        }
+    }
+}
+
+impl<V: FromValue> FromValue for Option<V> {
+    fn from_value(value: Option<&Value>) -> Result<Self> {
+        match value {
+            None | Some(Value::Null) => Ok(None),
+            some => V::from_value(some).map(Some),
+        }
+    }
+}
+
+/// Decodes a single [`Row`] into `Self`.
+///
+/// Blanket-implemented for tuples `(A,)` through `(A, B, C, D, E, F, G, H)`, where each
+/// element is a column decoded by position via [`FromValue`]. Structs should implement this
+/// by hand, decoding fields by name with [`Row::value_by_name`], e.g.:
+///
+/// ```ignore
+/// struct User { id: i64, name: String }
+///
+/// impl FromRow for User {
+///     fn from_row(row: &Row<'_>) -> Result<Self> {
+///         Ok(User {
+///             id: FromValue::from_value(row.value_by_name("id"))?,
+///             name: FromValue::from_value(row.value_by_name("name"))?,
+///         })
+///     }
+/// }
+/// ```
+pub trait FromRow: Sized {
+    fn from_row(row: &Row<'_>) -> Result<Self>;
+}
+
+macro_rules! impl_from_row_for_tuple {
+    ($count:expr; $($idx:tt => $ty:ident),+) => {
+        impl<$($ty: FromValue),+> FromRow for ($($ty,)+) {
+            fn from_row(row: &Row<'_>) -> Result<Self> {
+                if row.len() != $count {
+                    return Err(HranaError::Api(format!(
+                        "expected {} columns, row has {}",
+                        $count,
+                        row.len(),
+                    )));
+                }
+                Ok(($($ty::from_value(row.value($idx))?,)+))
+            }
+        }
+    };
+}
+
+impl_from_row_for_tuple!(1; 0 => A);
+impl_from_row_for_tuple!(2; 0 => A, 1 => B);
+impl_from_row_for_tuple!(3; 0 => A, 1 => B, 2 => C);
+impl_from_row_for_tuple!(4; 0 => A, 1 => B, 2 => C, 3 => D);
+impl_from_row_for_tuple!(5; 0 => A, 1 => B, 2 => C, 3 => D, 4 => E);
+impl_from_row_for_tuple!(6; 0 => A, 1 => B, 2 => C, 3 => D, 4 => E, 5 => F);
+impl_from_row_for_tuple!(7; 0 => A, 1 => B, 2 => C, 3 => D, 4 => E, 5 => F, 6 => G);
+impl_from_row_for_tuple!(8; 0 => A, 1 => B, 2 => C, 3 => D, 4 => E, 5 => F, 6 => G, 7 => H);
+
 #[derive(Debug)]
 pub struct Cursor<T>
 where
     T: for<'a> HttpSend<'a>,
 {
-    stream: T,
+    auth_token: String,
+    /// `base_url` returned in the `CursorResp`, if any, overrides `url` for the rest of
+    /// this session.
+    base_url: String,
+    baton: Option<String>,
+    stream: Pin<Box<<T as HttpSend<'static>>::Stream>>,
+    /// Bytes read from `stream` that haven't been split into a complete line yet.
+    buf: BytesMut,
+    /// Set once `stream` has yielded its last chunk.
+    eof: bool,
+    /// Columns of the step currently being read, set by the most recent `StepBegin` entry.
+    cols: Vec<Col>,
 }
 
 impl<T> Cursor<T>
 where
     T: for<'a> HttpSend<'a>,
 {
-    pub async fn open(stream: T, url: String, auth_token: String, body: Batch) -> Result<T> {
-        todo!()
+    pub async fn open(sender: T, url: String, auth_token: String, body: Batch) -> Result<Self> {
+        let cursor_url: Arc<str> = Arc::from(format!("{url}/v3/cursor"));
+        let auth: Arc<str> = Arc::from(auth_token.as_str());
+        let req_body = serde_json::to_string(&CursorReq { baton: None, batch: body })?;
+
+        let stream = sender.http_send(cursor_url, auth, req_body).await?;
+
+        let mut cursor = Cursor {
+            auth_token,
+            base_url: url,
+            baton: None,
+            stream: Box::pin(stream),
+            buf: BytesMut::new(),
+            eof: false,
+            cols: Vec::new(),
+        };
+
+        // The first newline-delimited value in the body is always the `CursorResp`; it
+        // carries the baton (and possibly a new base_url) the rest of the session must use.
+        let line = cursor
+            .read_line()
+            .await?
+            .ok_or_else(|| HranaError::Api("cursor stream closed before a response".into()))?;
+        let resp: CursorResp = serde_json::from_slice(&line)?;
+
+        cursor.baton = resp.baton;
+        if let Some(base_url) = resp.base_url {
+            cursor.base_url = base_url;
+        }
+
+        Ok(cursor)
+    }
+
+    /// The baton of the current cursor session, if the server assigned one.
+    pub fn baton(&self) -> Option<&str> {
+        self.baton.as_deref()
+    }
+
+    /// The URL subsequent requests in this session should be sent to.
+    pub fn base_url(&self) -> &str {
+        &self.base_url
+    }
+
+    /// Reads and returns the next complete line from the underlying body, buffering partial
+    /// reads until either a newline or a clean EOF is observed. A trailing partial line with
+    /// no newline at EOF is flushed as the last line.
+    async fn read_line(&mut self) -> Result<Option<BytesMut>> {
+        loop {
+            if let Some(line) = take_line(&mut self.buf) {
+                return Ok(Some(line));
+            }
+
+            if self.eof {
+                return Ok(None);
+            }
+
+            match self.stream.next().await {
+                Some(Ok(chunk)) => self.buf.extend_from_slice(&chunk),
+                Some(Err(e)) => return Err(e),
+                None => {
+                    self.eof = true;
+                    if !self.buf.is_empty() {
+                        return Ok(Some(std::mem::take(&mut self.buf)));
+                    }
+                    return Ok(None);
+                }
+            }
+        }
+    }
+
+    /// Reads and decodes the next result row into `R` via [`FromRow`], skipping over
+    /// step-boundary entries (`StepBegin`, `StepEnd`) transparently. `StepBegin`'s columns
+    /// are remembered so rows can be decoded by name as well as by position. Returns
+    /// `Ok(None)` once the cursor is exhausted; an `Err` is returned as soon as it is
+    /// observed, whether raised by the transport, the server, or `R::from_row`.
+    pub async fn next_typed<R: FromRow>(&mut self) -> Result<Option<R>> {
+        loop {
+            match self.next().await {
+                Some(Ok(CursorEntry::StepBegin(StepBeginEntry { cols, .. }))) => {
+                    self.cols = cols;
+                }
+                Some(Ok(CursorEntry::StepEnd(_))) => self.cols.clear(),
+                Some(Ok(CursorEntry::Row(RowEntry { row }))) => {
+                    let row = Row {
+                        cols: &self.cols,
+                        values: &row,
+                    };
+                    return R::from_row(&row).map(Some);
+                }
+                Some(Ok(_)) => continue,
+                Some(Err(e)) => return Err(e),
+                None => return Ok(None),
+            }
+        }
+    }
+
+    /// Drains the rest of this cursor into a `Vec<R>`, decoding every row via [`FromRow`] —
+    /// the `query_as::<T>(...)`-style entry point, for callers who want every row up front
+    /// instead of pulling them one at a time with [`Self::next_typed`].
+    ///
+    /// Note: this only covers query execution that goes through the Hrana cursor protocol in
+    /// this file. Hooking `FromRow` into `Connection`/`ResultSet` so it's reachable uniformly
+    /// across every backend (local, remote, embedded replica) needs a change in whatever
+    /// module defines those types, which isn't among the files this change touches.
+    pub async fn query_as<R: FromRow>(&mut self) -> Result<Vec<R>> {
+        let mut rows = Vec::new();
+        while let Some(row) = self.next_typed::<R>().await? {
+            rows.push(row);
+        }
+        Ok(rows)
     }
 }
 
@@ -82,6 +368,35 @@ where
     type Item = Result<CursorEntry>;
 
     fn poll_next(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
-        todo!()
+        // All of `Cursor`'s fields are `Unpin` (the body stream is boxed), so it's safe to get
+        // a plain `&mut` to drive the manual buffering/parsing below.
+        let this = self.get_mut();
+
+        loop {
+            if let Some(line) = take_line(&mut this.buf) {
+                if line.is_empty() {
+                    continue;
+                }
+                return Poll::Ready(Some(parse_entry(&line)));
+            }
+
+            if this.eof {
+                return Poll::Ready(None);
+            }
+
+            match this.stream.as_mut().poll_next(cx) {
+                Poll::Ready(Some(Ok(chunk))) => this.buf.extend_from_slice(&chunk),
+                Poll::Ready(Some(Err(e))) => return Poll::Ready(Some(Err(e))),
+                Poll::Ready(None) => {
+                    this.eof = true;
+                    if this.buf.is_empty() {
+                        return Poll::Ready(None);
+                    }
+                    let line = std::mem::take(&mut this.buf);
+                    return Poll::Ready(Some(parse_entry(&line)));
+                }
+                Poll::Pending => return Poll::Pending,
+            }
+        }
     }
 }
@@ -8,11 +8,13 @@ use libsql_replication::replicator::{Either, Replicator};
 pub use libsql_replication::snapshot::SnapshotFile;
 
 use libsql_replication::rpc::proxy::{
-    query::Params, DescribeRequest, DescribeResult, ExecuteResults, Positional, Program,
+    query::Params, DescribeRequest, DescribeResult, ExecuteResults, Named, Positional, Program,
     ProgramReq, Query, Step,
 };
-use tokio::sync::Mutex;
+use serde::{Deserialize, Serialize};
+use tokio::sync::{watch, Mutex};
 
+use crate::database::builder::ReconnectStrategy;
 use crate::parser::Statement;
 use crate::Result;
 
@@ -35,6 +37,175 @@ pub enum Frames {
     Snapshot(SnapshotFile),
 }
 
+/// Returned by [`Writer::execute_program`] when a statement's bound params don't match its
+/// placeholders.
+#[derive(Debug)]
+pub enum ParamsError {
+    /// A positional statement was bound with the wrong number of values.
+    PositionalCountMismatch {
+        step: usize,
+        expected: usize,
+        got: usize,
+    },
+    /// A named statement was bound with a value for a placeholder the statement doesn't have.
+    UnknownNamedParam { step: usize, name: String },
+    /// A named statement has a placeholder with no bound value.
+    MissingNamedParam { step: usize, name: String },
+}
+
+impl std::fmt::Display for ParamsError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            ParamsError::PositionalCountMismatch { step, expected, got } => write!(
+                f,
+                "step {step}: statement expects {expected} positional param(s), got {got}"
+            ),
+            ParamsError::UnknownNamedParam { step, name } => {
+                write!(f, "step {step}: no placeholder named `{name}` in statement")
+            }
+            ParamsError::MissingNamedParam { step, name } => {
+                write!(f, "step {step}: placeholder `{name}` has no bound value")
+            }
+        }
+    }
+}
+
+impl std::error::Error for ParamsError {}
+
+/// An opaque, serializable checkpoint returned by [`Writer::execute_program`] after a write.
+/// Passing it to [`EmbeddedReplicator::wait_for`] — on this connection, a different one, or a
+/// different embedded replica pointed at the same primary, even in another process — blocks
+/// until that write's effects are visible locally, giving session-level monotonic-read
+/// consistency without either side needing to share anything but this token.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ReplicationToken(FrameNo);
+
+impl ReplicationToken {
+    /// The commit index this token checkpoints.
+    pub fn frame_no(&self) -> FrameNo {
+        self.0
+    }
+}
+
+/// The bind placeholders (`?`, `?N`, `:name`, `@name`, `$name`) referenced by a statement's
+/// SQL text, in the order they appear. Positional placeholders (`?`, `?N`) are reported as
+/// `None`; named placeholders are reported as `Some(name)` with the sigil stripped. Sigils
+/// inside a quoted string, a `--` line comment, or a `/* */` block comment are skipped, so a
+/// statement like `SELECT * FROM t WHERE id = ? -- is this ok?` reports exactly one
+/// placeholder rather than miscounting the `?` in the comment.
+fn placeholders(sql: &str) -> Vec<Option<&str>> {
+    let mut placeholders = Vec::new();
+    let bytes = sql.as_bytes();
+    let mut i = 0;
+    let mut in_string = None;
+
+    while i < bytes.len() {
+        let b = bytes[i];
+        if let Some(quote) = in_string {
+            if b == quote {
+                in_string = None;
+            }
+            i += 1;
+            continue;
+        }
+
+        if b == b'-' && bytes.get(i + 1) == Some(&b'-') {
+            i = bytes[i..]
+                .iter()
+                .position(|&b| b == b'\n')
+                .map(|pos| i + pos)
+                .unwrap_or(bytes.len());
+            continue;
+        }
+
+        if b == b'/' && bytes.get(i + 1) == Some(&b'*') {
+            i = sql[i + 2..]
+                .find("*/")
+                .map(|pos| i + 2 + pos + 2)
+                .unwrap_or(bytes.len());
+            continue;
+        }
+
+        match b {
+            b'\'' | b'"' => {
+                in_string = Some(b);
+                i += 1;
+            }
+            b'?' => {
+                let start = i + 1;
+                let mut end = start;
+                while end < bytes.len() && bytes[end].is_ascii_digit() {
+                    end += 1;
+                }
+                placeholders.push(None);
+                i = end;
+            }
+            b':' | b'@' | b'$' => {
+                let start = i + 1;
+                let mut end = start;
+                while end < bytes.len() && (bytes[end].is_ascii_alphanumeric() || bytes[end] == b'_') {
+                    end += 1;
+                }
+                if end > start {
+                    placeholders.push(Some(&sql[start..end]));
+                }
+                i = end.max(i + 1);
+            }
+            _ => i += 1,
+        }
+    }
+
+    placeholders
+}
+
+/// Checks that `params` binds exactly the placeholders `stmt` declares, positionally or by
+/// name. `step` is only used to identify the offending statement in the returned error.
+fn validate_params(
+    step: usize,
+    stmt: &Statement,
+    params: &Params,
+) -> std::result::Result<(), ParamsError> {
+    let placeholders = placeholders(&stmt.stmt);
+
+    match params {
+        Params::Positional(Positional { values }) => {
+            let expected = placeholders.iter().filter(|p| p.is_none()).count();
+            if values.len() != expected {
+                return Err(ParamsError::PositionalCountMismatch {
+                    step,
+                    expected,
+                    got: values.len(),
+                });
+            }
+        }
+        Params::Named(Named { names, .. }) => {
+            let declared: std::collections::HashSet<&str> =
+                placeholders.iter().filter_map(|p| *p).collect();
+            for name in names {
+                if !declared.contains(name.trim_start_matches([':', '@', '$'])) {
+                    return Err(ParamsError::UnknownNamedParam {
+                        step,
+                        name: name.clone(),
+                    });
+                }
+            }
+            for name in declared {
+                if !names
+                    .iter()
+                    .any(|bound| bound.trim_start_matches([':', '@', '$']) == name)
+                {
+                    return Err(ParamsError::MissingNamedParam {
+                        step,
+                        name: name.to_owned(),
+                    });
+                }
+            }
+        }
+    }
+
+    Ok(())
+}
+
 #[derive(Clone)]
 pub(crate) struct Writer {
     pub(crate) client: client::Client,
@@ -42,36 +213,65 @@ pub(crate) struct Writer {
 }
 
 impl Writer {
+    /// Executes a multi-statement program, binding each statement to its own params.
+    ///
+    /// `steps` pairs every [`Statement`] with the [`Params`] (positional or named) meant for
+    /// it; each pair is validated against that statement's placeholders and mapped one-to-one
+    /// onto the corresponding [`Step`]'s `Query::params`, so batched programs delegated to the
+    /// remote primary bind the params the caller actually supplied instead of an empty default.
+    ///
+    /// Returns a [`ReplicationToken`] checkpointing the write alongside the RPC result, so a
+    /// caller on another connection can pass it to [`EmbeddedReplicator::wait_for`] to observe
+    /// the write (read-your-writes across connections, not just within this one). The token is
+    /// only available when this `Writer` has an [`EmbeddedReplicator`] to check in with after
+    /// the write lands; a pure [`crate::Builder::new_remote`] `Writer` has nothing local to
+    /// checkpoint against and always returns `None`.
+    ///
+    /// The token is derived straight from `results.current_frame_no` — the replication index
+    /// the primary reports alongside the write's own response — rather than paying for a
+    /// separate `sync_oneshot` round on every write, which would add latency even when the
+    /// caller never reads the token, and could under-report the write if the just-committed
+    /// frame hasn't propagated to the replication log yet by the time of that separate sync.
+    /// Only falls back to an actual sync round if the primary's response didn't carry an
+    /// index at all.
     pub(crate) async fn execute_program(
         &self,
-        steps: Vec<Statement>,
-        params: impl Into<Params>,
-    ) -> anyhow::Result<ExecuteResults> {
-        let mut params = Some(params.into());
-
+        steps: Vec<(Statement, Params)>,
+    ) -> anyhow::Result<(ExecuteResults, Option<ReplicationToken>)> {
         let steps = steps
             .into_iter()
-            .map(|stmt| Step {
-                query: Some(Query {
-                    stmt: stmt.stmt,
-                    // TODO(lucio): Pass params
-                    params: Some(
-                        params
-                            .take()
-                            .unwrap_or(Params::Positional(Positional::default())),
-                    ),
+            .enumerate()
+            .map(|(index, (stmt, params))| {
+                validate_params(index, &stmt, &params)?;
+                Ok(Step {
+                    query: Some(Query {
+                        stmt: stmt.stmt,
+                        params: Some(params),
+                        ..Default::default()
+                    }),
                     ..Default::default()
-                }),
-                ..Default::default()
+                })
             })
-            .collect();
+            .collect::<anyhow::Result<Vec<_>>>()?;
 
-        self.client
+        let results = self
+            .client
             .execute_program(ProgramReq {
                 client_id: self.client.client_id(),
                 pgm: Some(Program { steps }),
             })
-            .await
+            .await?;
+
+        let token = match (&self.replicator, results.current_frame_no) {
+            (Some(_), Some(frame_no)) => Some(ReplicationToken(frame_no)),
+            (Some(replicator), None) => {
+                let (frame_no, _) = replicator.sync_oneshot().await?;
+                Some(ReplicationToken(frame_no))
+            }
+            (None, _) => None,
+        };
+
+        Ok((results, token))
     }
 
     pub(crate) async fn describe(&self, stmt: impl Into<String>) -> anyhow::Result<DescribeResult> {
@@ -90,9 +290,24 @@ impl Writer {
     }
 }
 
+/// Health of an [`EmbeddedReplicator`]'s connection to the remote primary, observable via
+/// [`EmbeddedReplicator::connection_state`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ConnectionState {
+    /// The last sync round completed successfully.
+    Connected,
+    /// A sync round failed and [`EmbeddedReplicator::sync_with_reconnect`] is retrying with
+    /// backoff.
+    Reconnecting,
+    /// Retries were exhausted for the last round; the next scheduled sync starts over.
+    Disconnected,
+}
+
 #[derive(Clone)]
 pub(crate) struct EmbeddedReplicator {
     replicator: Arc<Mutex<Replicator<Either<RemoteClient, LocalClient>>>>,
+    connection_state: Arc<watch::Sender<ConnectionState>>,
+    sync_progress: Arc<watch::Sender<(FrameNo, usize)>>,
 }
 
 impl EmbeddedReplicator {
@@ -102,8 +317,14 @@ impl EmbeddedReplicator {
                 .await
                 .unwrap(),
         ));
+        let (connection_state, _) = watch::channel(ConnectionState::Disconnected);
+        let (sync_progress, _) = watch::channel((FrameNo::default(), 0));
 
-        Self { replicator }
+        Self {
+            replicator,
+            connection_state: Arc::new(connection_state),
+            sync_progress: Arc::new(sync_progress),
+        }
     }
 
     pub async fn with_local(client: LocalClient, db_path: PathBuf, auto_checkpoint: u32, encryption_key: Option<bytes::Bytes>) -> Self {
@@ -112,8 +333,52 @@ impl EmbeddedReplicator {
                 .await
                 .unwrap(),
         ));
+        let (connection_state, _) = watch::channel(ConnectionState::Disconnected);
+        let (sync_progress, _) = watch::channel((FrameNo::default(), 0));
 
-        Self { replicator }
+        Self {
+            replicator,
+            connection_state: Arc::new(connection_state),
+            sync_progress: Arc::new(sync_progress),
+        }
+    }
+
+    /// A live view of [`ConnectionState`], updated every time [`Self::sync_with_reconnect`]
+    /// completes or retries a round. Replicas driven only by plain [`Self::sync_oneshot`] calls
+    /// never move off [`ConnectionState::Disconnected`].
+    pub fn connection_state(&self) -> watch::Receiver<ConnectionState> {
+        self.connection_state.subscribe()
+    }
+
+    /// Like [`Self::sync_oneshot`], but retries a failed round with `strategy`'s backoff (up to
+    /// `strategy.max_retries`) instead of surfacing the first transient error, and reports
+    /// progress through [`Self::connection_state`]. Intended to back the `periodic_sync`
+    /// background task so a dropped connection to the primary self-heals instead of silently
+    /// stalling replication until the process restarts.
+    pub async fn sync_with_reconnect(
+        &self,
+        strategy: &ReconnectStrategy,
+    ) -> Result<(FrameNo, usize)> {
+        let mut attempt = 0u32;
+        loop {
+            match self.sync_oneshot().await {
+                Ok(result) => {
+                    let _ = self.connection_state.send_replace(ConnectionState::Connected);
+                    return Ok(result);
+                }
+                Err(e) if attempt < strategy.max_retries => {
+                    let _ = self.connection_state.send_replace(ConnectionState::Reconnecting);
+                    let delay = strategy.backoff(attempt);
+                    tracing::warn!(attempt, ?delay, error = %e, "sync round failed, retrying");
+                    tokio::time::sleep(delay).await;
+                    attempt += 1;
+                }
+                Err(e) => {
+                    let _ = self.connection_state.send_replace(ConnectionState::Disconnected);
+                    return Err(e);
+                }
+            }
+        }
     }
 
     /// Returns the new replication index, and how many log entries have been synced
@@ -158,12 +423,20 @@ impl EmbeddedReplicator {
             }
         }
 
-        Ok((replicator.current_commit_index(), count_synced))
+        let frame_no = replicator.current_commit_index();
+        let _ = self.sync_progress.send_replace((frame_no, count_synced));
+
+        Ok((frame_no, count_synced))
     }
 
     pub async fn sync_frames(&self, frames: Frames) -> Result<FrameNo> {
         let mut replicator = self.replicator.lock().await;
 
+        let count_applied = match &frames {
+            Frames::Vec(frames) => frames.len(),
+            Frames::Snapshot(_) => 0,
+        };
+
         match replicator.client_mut() {
             Either::Right(c) => {
                 c.load_frames(frames);
@@ -179,7 +452,10 @@ impl EmbeddedReplicator {
             .await
             .map_err(|e| crate::Error::Replication(e.into()))?;
 
-        Ok(replicator.current_commit_index())
+        let frame_no = replicator.current_commit_index();
+        let _ = self.sync_progress.send_replace((frame_no, count_applied));
+
+        Ok(frame_no)
     }
 
     pub async fn flush(&self) -> Result<FrameNo> {
@@ -191,7 +467,63 @@ impl EmbeddedReplicator {
         Ok(replicator.current_commit_index())
     }
 
+    /// Returns the replica's current committed [`FrameNo`] — the last frame flushed/committed
+    /// locally — without triggering a sync, so callers can check a checkpoint returned by a
+    /// prior write (e.g. for read-your-writes) without paying for a round-trip to the primary.
     pub async fn committed_frame_no(&self) -> Option<FrameNo> {
-        todo!()
+        let replicator = self.replicator.lock().await;
+        Some(replicator.current_commit_index())
+    }
+
+    /// A stream of `(FrameNo, frames_applied)` updates, emitted every time [`Self::sync_oneshot`]
+    /// or [`Self::sync_frames`] (and so, transitively, the `periodic_sync` background task)
+    /// commits new frames. Lets callers await "the replica has caught up to frame N" — e.g. one
+    /// returned by their own prior write — without polling [`Self::committed_frame_no`].
+    pub fn subscribe_sync(&self) -> watch::Receiver<(FrameNo, usize)> {
+        self.sync_progress.subscribe()
+    }
+
+    /// Blocks until this replicator's committed [`FrameNo`] reaches `token`, syncing from the
+    /// primary as needed. This is the mechanism behind `Connection::wait_for`'s cross-connection
+    /// read-your-writes: the token is cheap to pass around (it's just a frame number), so any
+    /// connection backed by an `EmbeddedReplicator` pointed at the same primary can catch up to
+    /// a write made through a different one.
+    ///
+    /// Bounded the same way [`ReconnectStrategy::backoff`] bounds `sync_with_reconnect`'s
+    /// retries: gives up after [`Self::WAIT_FOR_MAX_ATTEMPTS`] rounds with exponential backoff
+    /// between them, rather than busy-looping forever against a primary that's unreachable but
+    /// whose individual `sync_oneshot` calls happen to succeed without ever advancing past
+    /// `token`.
+    pub async fn wait_for(&self, token: ReplicationToken) -> Result<FrameNo> {
+        const BASE_DELAY: std::time::Duration = std::time::Duration::from_millis(50);
+        const MAX_DELAY: std::time::Duration = std::time::Duration::from_secs(5);
+
+        for attempt in 0..Self::WAIT_FOR_MAX_ATTEMPTS {
+            let current = self.replicator.lock().await.current_commit_index();
+            if current >= token.0 {
+                return Ok(current);
+            }
+
+            self.sync_oneshot().await?;
+
+            if attempt + 1 < Self::WAIT_FOR_MAX_ATTEMPTS {
+                let factor = 1u32.checked_shl(attempt).unwrap_or(u32::MAX);
+                let delay = BASE_DELAY.saturating_mul(factor).min(MAX_DELAY);
+                tokio::time::sleep(delay).await;
+            }
+        }
+
+        let current = self.replicator.lock().await.current_commit_index();
+        if current >= token.0 {
+            return Ok(current);
+        }
+
+        Err(crate::errors::Error::Misuse(format!(
+            "gave up waiting for replication to catch up to frame {} after {} attempts",
+            token.0,
+            Self::WAIT_FOR_MAX_ATTEMPTS,
+        )))
     }
+
+    const WAIT_FOR_MAX_ATTEMPTS: u32 = 10;
 }
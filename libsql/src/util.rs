@@ -0,0 +1,138 @@
+//! Connector/transport plumbing shared by every `Remote`/`RemoteReplica` database: a
+//! type-erased `tower::Service<http::Uri>` that hands back a [`Socket`] hyper can speak HTTP
+//! over.
+//!
+//! The concrete connector differs per target: [`native`] dials a real TCP/TLS socket, while
+//! [`wasm`] has no raw socket available in a browser/edge runtime and instead drives each
+//! exchange through the host's `fetch` binding. Both sides are wrapped in the same
+//! [`ConnectorService`], so `Builder<Remote>::build`, `Writer::execute_program`, and
+//! `Writer::describe` stay identical across targets.
+
+use std::pin::Pin;
+use std::task::{Context, Poll};
+
+use tokio::io::{AsyncRead, AsyncWrite};
+
+#[cfg(not(target_arch = "wasm32"))]
+pub(crate) mod native;
+#[cfg(target_arch = "wasm32")]
+pub(crate) mod wasm;
+
+pub(crate) type BoxError = Box<dyn std::error::Error + Send + Sync>;
+
+/// Marker for anything hyper can drive an HTTP/1.1 connection over. `Unpin` because hyper polls
+/// it directly through a `Box<dyn Socket>`.
+///
+/// Native sockets are also `Send`, since tokio's multi-threaded runtime can move a connection
+/// across worker threads. `wasm32` is single-threaded and its `fetch`/JS bindings are `!Send`,
+/// so the bound is dropped there rather than papered over with an `unsafe impl Send`.
+#[cfg(not(target_arch = "wasm32"))]
+pub trait Socket: AsyncRead + AsyncWrite + Send + Unpin {}
+#[cfg(not(target_arch = "wasm32"))]
+impl<T: AsyncRead + AsyncWrite + Send + Unpin> Socket for T {}
+
+#[cfg(target_arch = "wasm32")]
+pub trait Socket: AsyncRead + AsyncWrite + Unpin {}
+#[cfg(target_arch = "wasm32")]
+impl<T: AsyncRead + AsyncWrite + Unpin> Socket for T {}
+
+impl AsyncRead for Box<dyn Socket> {
+    fn poll_read(
+        self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+        buf: &mut tokio::io::ReadBuf<'_>,
+    ) -> Poll<std::io::Result<()>> {
+        Pin::new(&mut **self.get_mut()).poll_read(cx, buf)
+    }
+}
+
+impl AsyncWrite for Box<dyn Socket> {
+    fn poll_write(
+        self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+        buf: &[u8],
+    ) -> Poll<std::io::Result<usize>> {
+        Pin::new(&mut **self.get_mut()).poll_write(cx, buf)
+    }
+
+    fn poll_flush(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<std::io::Result<()>> {
+        Pin::new(&mut **self.get_mut()).poll_flush(cx)
+    }
+
+    fn poll_shutdown(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<std::io::Result<()>> {
+        Pin::new(&mut **self.get_mut()).poll_shutdown(cx)
+    }
+}
+
+/// A type-erased, cloneable `tower::Service<http::Uri>` producing a boxed [`Socket`]. Lets
+/// every call site (the default native/wasm connector, a user-supplied
+/// [`Builder::connector`](crate::Builder::connector), or the pool wrapper in
+/// `crate::database::builder`) hand around "the thing that opens a connection" without naming
+/// the concrete connector type.
+///
+/// The native and `wasm32` versions are built differently: native wraps an arbitrary
+/// `tower::Service` (the default TCP/TLS dialer, a pooled wrapper around it, or a
+/// caller-supplied one) behind [`tower::util::BoxCloneService`], which requires `Send` futures
+/// for tokio's multi-threaded runtime. `wasm32` only ever dials a [`wasm::FetchSocket`] — its
+/// future holds `!Send` JS bindings — so it skips the generic wrapper entirely and is just a
+/// unit struct that builds one directly.
+#[cfg(not(target_arch = "wasm32"))]
+pub struct ConnectorService(tower::util::BoxCloneService<http::Uri, Box<dyn Socket>, BoxError>);
+
+#[cfg(not(target_arch = "wasm32"))]
+impl ConnectorService {
+    pub(crate) fn new<S>(svc: S) -> Self
+    where
+        S: tower::Service<http::Uri, Response = Box<dyn Socket>> + Clone + Send + 'static,
+        S::Future: Send + 'static,
+        S::Error: Into<BoxError>,
+    {
+        Self(tower::util::BoxCloneService::new(
+            tower::ServiceExt::map_err(svc, Into::into),
+        ))
+    }
+}
+
+#[cfg(not(target_arch = "wasm32"))]
+impl Clone for ConnectorService {
+    fn clone(&self) -> Self {
+        Self(self.0.clone())
+    }
+}
+
+#[cfg(not(target_arch = "wasm32"))]
+impl tower::Service<http::Uri> for ConnectorService {
+    type Response = Box<dyn Socket>;
+    type Error = BoxError;
+    type Future =
+        <tower::util::BoxCloneService<http::Uri, Box<dyn Socket>, BoxError> as tower::Service<
+            http::Uri,
+        >>::Future;
+
+    fn poll_ready(&mut self, cx: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+        self.0.poll_ready(cx)
+    }
+
+    fn call(&mut self, uri: http::Uri) -> Self::Future {
+        self.0.call(uri)
+    }
+}
+
+#[cfg(target_arch = "wasm32")]
+#[derive(Clone, Copy, Default)]
+pub struct ConnectorService;
+
+#[cfg(target_arch = "wasm32")]
+impl tower::Service<http::Uri> for ConnectorService {
+    type Response = Box<dyn Socket>;
+    type Error = BoxError;
+    type Future = Pin<Box<dyn std::future::Future<Output = Result<Self::Response, Self::Error>>>>;
+
+    fn poll_ready(&mut self, _cx: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+        Poll::Ready(Ok(()))
+    }
+
+    fn call(&mut self, uri: http::Uri) -> Self::Future {
+        Box::pin(async move { Ok(Box::new(wasm::FetchSocket::new(uri)) as Box<dyn Socket>) })
+    }
+}
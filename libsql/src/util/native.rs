@@ -0,0 +1,6 @@
+//! Native (non-`wasm32`) connector: a real TCP/TLS socket, dialed by the platform's default
+//! HTTP connector (`crate::database::connector`, wired up as the default in
+//! `Builder<Remote>::connector_or_default` and `Builder<RemoteReplica>::build`). There is
+//! nothing `wasm`-specific to bridge here — a native [`super::Socket`] already *is* the raw
+//! socket hyper expects, so this module exists mainly to mirror [`super::wasm`] and give the
+//! native path a name to gate `#[cfg(not(target_arch = "wasm32"))]` code against.
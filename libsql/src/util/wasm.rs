@@ -0,0 +1,182 @@
+//! `wasm32` connector: there's no raw TCP socket in a browser/edge runtime, so each HTTP
+//! exchange hyper would normally drive over a socket is instead satisfied by a single call to
+//! the host's `fetch` binding. [`FetchSocket`] buffers the raw bytes hyper writes (a full
+//! HTTP/1.1 request), fires `fetch` the first time hyper either polls the read half or shuts
+//! the write half down, sending whatever request bytes were written by then, and serves the read half from the response re-serialized back into
+//! HTTP/1.1 bytes, so from hyper's point of view it's indistinguishable from a real socket.
+
+use std::cell::{Cell, RefCell};
+use std::io;
+use std::pin::Pin;
+use std::rc::Rc;
+use std::task::{Context, Poll, Waker};
+
+use tokio::io::{AsyncRead, AsyncWrite, ReadBuf};
+use wasm_bindgen::JsCast;
+use wasm_bindgen_futures::JsFuture;
+
+use super::BoxError;
+
+enum ResponseState {
+    Pending,
+    Ready(io::Cursor<Vec<u8>>),
+    Failed(String),
+}
+
+/// A [`super::Socket`] backed by `fetch` instead of a raw TCP connection. See the module docs
+/// for the write-then-read protocol it implements.
+pub(crate) struct FetchSocket {
+    uri: http::Uri,
+    pending_request: RefCell<Vec<u8>>,
+    dispatched: Cell<bool>,
+    response: Rc<RefCell<ResponseState>>,
+}
+
+impl FetchSocket {
+    pub(crate) fn new(uri: http::Uri) -> Self {
+        Self {
+            uri,
+            pending_request: RefCell::new(Vec::new()),
+            dispatched: Cell::new(false),
+            response: Rc::new(RefCell::new(ResponseState::Pending)),
+        }
+    }
+
+    /// Kicks off `fetch` with the request bytes buffered so far, on the first call from either
+    /// `poll_read` or `poll_shutdown`; a no-op on every call after the first.
+    fn start_fetch_if_needed(&self, waker: Waker) {
+        if self.dispatched.replace(true) {
+            return;
+        }
+
+        let request = std::mem::take(&mut *self.pending_request.borrow_mut());
+        let uri = self.uri.clone();
+        let response = self.response.clone();
+
+        wasm_bindgen_futures::spawn_local(async move {
+            let result = fetch(uri, request).await;
+            *response.borrow_mut() = match result {
+                Ok(bytes) => ResponseState::Ready(io::Cursor::new(bytes)),
+                Err(e) => ResponseState::Failed(e.to_string()),
+            };
+            waker.wake();
+        });
+    }
+}
+
+impl AsyncWrite for FetchSocket {
+    fn poll_write(
+        self: Pin<&mut Self>,
+        _cx: &mut Context<'_>,
+        buf: &[u8],
+    ) -> Poll<io::Result<usize>> {
+        self.pending_request.borrow_mut().extend_from_slice(buf);
+        Poll::Ready(Ok(buf.len()))
+    }
+
+    fn poll_flush(self: Pin<&mut Self>, _cx: &mut Context<'_>) -> Poll<io::Result<()>> {
+        Poll::Ready(Ok(()))
+    }
+
+    fn poll_shutdown(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<io::Result<()>> {
+        self.start_fetch_if_needed(cx.waker().clone());
+        Poll::Ready(Ok(()))
+    }
+}
+
+impl AsyncRead for FetchSocket {
+    fn poll_read(
+        self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+        buf: &mut ReadBuf<'_>,
+    ) -> Poll<io::Result<()>> {
+        self.start_fetch_if_needed(cx.waker().clone());
+
+        match &mut *self.response.borrow_mut() {
+            ResponseState::Pending => Poll::Pending,
+            ResponseState::Ready(body) => {
+                let n = std::io::Read::read(body, buf.initialize_unfilled())?;
+                buf.advance(n);
+                Poll::Ready(Ok(()))
+            }
+            ResponseState::Failed(message) => {
+                Poll::Ready(Err(io::Error::new(io::ErrorKind::Other, message.clone())))
+            }
+        }
+    }
+}
+
+/// Parses `request` as an HTTP/1.1 request, issues it via the host's `fetch`, and re-serializes
+/// the response as HTTP/1.1 bytes for hyper to parse back out on the read side.
+async fn fetch(uri: http::Uri, request: Vec<u8>) -> Result<Vec<u8>, BoxError> {
+    let (method, headers, body) = parse_request(&request)?;
+
+    let init = web_sys::RequestInit::new();
+    init.set_method(&method);
+    if !body.is_empty() {
+        init.set_body(&js_sys::Uint8Array::from(body.as_slice()));
+    }
+
+    let js_headers = web_sys::Headers::new().map_err(js_error)?;
+    for (name, value) in &headers {
+        js_headers.set(name, value).map_err(js_error)?;
+    }
+    init.set_headers(&js_headers);
+
+    let js_request =
+        web_sys::Request::new_with_str_and_init(&uri.to_string(), &init).map_err(js_error)?;
+
+    let window =
+        web_sys::window().ok_or_else(|| BoxError::from("fetch is only available in a window context"))?;
+    let response = JsFuture::from(window.fetch_with_request(&js_request))
+        .await
+        .map_err(js_error)?
+        .dyn_into::<web_sys::Response>()
+        .map_err(js_error)?;
+
+    let status = response.status();
+    let status_text = response.status_text();
+    let array_buffer = JsFuture::from(response.array_buffer().map_err(js_error)?)
+        .await
+        .map_err(js_error)?;
+    let body = js_sys::Uint8Array::new(&array_buffer).to_vec();
+
+    let mut out = format!("HTTP/1.1 {status} {status_text}\r\ncontent-length: {}\r\n\r\n", body.len())
+        .into_bytes();
+    out.extend_from_slice(&body);
+    Ok(out)
+}
+
+/// A minimal HTTP/1.1 request-line + header parser: just enough to recover what `fetch` needs
+/// (method, headers, body) from the bytes hyper wrote, without pulling in a full HTTP parsing
+/// crate for a browser-only code path. The request-line's path is intentionally dropped: `uri`
+/// (the same one hyper dialed the connector with) already carries it.
+fn parse_request(request: &[u8]) -> Result<(String, Vec<(String, String)>, Vec<u8>), BoxError> {
+    let head_end = request
+        .windows(4)
+        .position(|w| w == b"\r\n\r\n")
+        .ok_or("malformed request: no header terminator")?;
+
+    let head = std::str::from_utf8(&request[..head_end])?;
+    let body = request[head_end + 4..].to_vec();
+
+    let mut lines = head.split("\r\n");
+    let request_line = lines.next().ok_or("malformed request: missing request line")?;
+    let method = request_line
+        .split(' ')
+        .next()
+        .ok_or("malformed request line")?
+        .to_owned();
+
+    let headers = lines
+        .filter(|line| !line.is_empty())
+        .filter_map(|line| line.split_once(": "))
+        .map(|(name, value)| (name.to_owned(), value.to_owned()))
+        .collect();
+
+    Ok((method, headers, body))
+}
+
+fn js_error(value: wasm_bindgen::JsValue) -> BoxError {
+    format!("{value:?}").into()
+}